@@ -2,8 +2,14 @@
 
 pub mod callbacks;
 
+#[cfg(feature = "async")]
+pub use self::equity::{Events as EquityEvents, OverflowPolicy};
 pub use self::{
-    equity::{Stream as EquityStream, StreamBuilder as EquityStreamBuilder},
+    equity::{
+        Event as EquityEvent, EventHeader as EquityEventHeader, OwnedEquityEvent,
+        RingConsumer as EquityRingConsumer, Stream as EquityStream,
+        StreamBuilder as EquityStreamBuilder,
+    },
     keylist::{
         Catalog as KeylistCatalog, CatalogBuilder as KeylistCatalogBuilder,
         Filter as KeylistFilter, FilterBuilder as KeylistFilterBuilder,
@@ -14,9 +20,20 @@ mod equity;
 mod keylist;
 
 use crate::{
-    error::Result,
+    error::{Error, Result, Success},
     field::{self, Field},
+    field_set::{AttachedFieldSet, FieldSet, FieldSetLayout, FieldValue},
     object::Wrapper,
+    session::Lifeline,
+};
+use rxegy_sys::{XC_EVENT_DESC, xerr, xhandle};
+use std::{
+    collections::HashMap,
+    ffi::CStr,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    marker::PhantomData,
+    mem, ptr,
+    sync::{Arc, Mutex, PoisonError},
 };
 
 /// A marker trait used to identify realtime containers
@@ -30,6 +47,63 @@ pub trait Common: Wrapper {
 
     /// The number of slots in the container.
     fn slot_count(&self) -> Result<u32>;
+
+    /// The number of active slots in the container.
+    fn active_count(&self) -> Result<u32>;
+
+    /// The lowest-numbered empty slot in the container.
+    fn next_slot(&self) -> Result<u32>;
+
+    /// Whether the given slot is empty, pending, or active.
+    fn slot_state(&self, slot: u32) -> Result<SlotState>;
+
+    /// The key string which was used to request the given slot.
+    fn slot_key_string(&self, slot: u32) -> Result<String>;
+
+    /// Iterate over the slots which are pending or active.
+    fn slots(&self) -> Slots<'_>;
+
+    /// Suspend callbacks for every slot in the container, until the returned guard is dropped.
+    ///
+    /// The container's items continue to be updated while callbacks are suspended, and their
+    /// subscriptions are kept. This may not be called from within a callback.
+    fn suspend_callbacks(&self) -> Result<SuspendGuard<'_>>;
+
+    /// Resume callbacks for every suspended slot in the container.
+    ///
+    /// This may not be called from within a callback.
+    fn resume_callbacks(&self) -> Result<()>;
+
+    /// Suspend callbacks for the given slots, until they are resumed with [`Self::resume_slots()`].
+    fn suspend_slots(&self, slots: &[u32]) -> Result<()>;
+
+    /// Resume callbacks for the given slots.
+    fn resume_slots(&self, slots: &[u32]) -> Result<()>;
+
+    /// Whether callbacks for the given slot are suspended.
+    fn is_suspended(&self, slot: u32) -> Result<bool>;
+
+    /// Read every field in the given field set from a slot in a single call.
+    ///
+    /// Fields which could not be read are left zeroed.
+    fn read_field_set<L: FieldSetLayout>(&self, slot: u32, set: &FieldSet<L>) -> Result<L>;
+
+    /// Attach a field set to a slot, so XCAPI populates it before each callback on the slot.
+    ///
+    /// This may not be called from within a callback.
+    fn attach_field_set<L: FieldSetLayout>(
+        &self,
+        slot: u32,
+        set: &FieldSet<L>,
+    ) -> Result<AttachedFieldSet<L>>;
+
+    /// Read up to `max` consecutive elements of a field group array from a slot in a single call.
+    fn read_field_group_array<V: FieldValue>(
+        &self,
+        slot: u32,
+        group: u64,
+        max: usize,
+    ) -> Result<Vec<V>>;
 }
 
 impl<T: RealTime> Common for T {
@@ -40,18 +114,638 @@ impl<T: RealTime> Common for T {
     fn slot_count(&self) -> Result<u32> {
         field::get_u32(self, rxegy_sys::XC_CONTAINER, RealTimeField::SlotCount)
     }
+
+    fn active_count(&self) -> Result<u32> {
+        let mut count = 0;
+        Success::try_from(unsafe { rxegy_sys::xcActiveCount(self.as_xhandle(), &mut count) })?;
+        Ok(count)
+    }
+
+    fn next_slot(&self) -> Result<u32> {
+        let mut slot = 0;
+        Success::try_from(unsafe { rxegy_sys::xcNextSlot(self.as_xhandle(), &mut slot) })?;
+        Ok(slot)
+    }
+
+    fn slot_state(&self, slot: u32) -> Result<SlotState> {
+        SlotState::of(self.as_xhandle(), slot)
+    }
+
+    fn slot_key_string(&self, slot: u32) -> Result<String> {
+        let mut obuf = [0u8; 512];
+        let status = unsafe {
+            rxegy_sys::xcSlotKeyString(
+                self.as_xhandle(),
+                slot,
+                obuf.as_mut_ptr() as *mut _,
+                obuf.len() as u32,
+            )
+        };
+
+        Success::try_from(status)?;
+
+        Ok(CStr::from_bytes_until_nul(obuf.as_slice())?
+            .to_str()?
+            .to_owned())
+    }
+
+    fn slots(&self) -> Slots<'_> {
+        Slots {
+            handle: self.as_xhandle(),
+            next: 0,
+            count: self.slot_count().unwrap_or_default(),
+            _container: PhantomData,
+        }
+    }
+
+    fn suspend_callbacks(&self) -> Result<SuspendGuard<'_>> {
+        let mut guard = SuspendGuard::new();
+        guard.suspend(self.as_xhandle())?;
+        Ok(guard)
+    }
+
+    fn resume_callbacks(&self) -> Result<()> {
+        resume(self.as_xhandle(), &mut [])
+    }
+
+    fn suspend_slots(&self, slots: &[u32]) -> Result<()> {
+        if slots.is_empty() {
+            return Ok(());
+        }
+
+        let mut slots = slots.to_vec();
+        suspend(self.as_xhandle(), &mut slots)
+    }
+
+    fn resume_slots(&self, slots: &[u32]) -> Result<()> {
+        if slots.is_empty() {
+            return Ok(());
+        }
+
+        let mut slots = slots.to_vec();
+        resume(self.as_xhandle(), &mut slots)
+    }
+
+    fn is_suspended(&self, slot: u32) -> Result<bool> {
+        let mut flag = 0;
+        Success::try_from(unsafe { rxegy_sys::xcIsSuspended(self.as_xhandle(), slot, &mut flag) })?;
+        Ok(flag as u32 == rxegy_sys::XTRUE)
+    }
+
+    fn read_field_set<L: FieldSetLayout>(&self, slot: u32, set: &FieldSet<L>) -> Result<L> {
+        set.read(self.as_xhandle(), slot)
+    }
+
+    fn attach_field_set<L: FieldSetLayout>(
+        &self,
+        slot: u32,
+        set: &FieldSet<L>,
+    ) -> Result<AttachedFieldSet<L>> {
+        set.attach(self.as_xhandle(), slot)
+    }
+
+    fn read_field_group_array<V: FieldValue>(
+        &self,
+        slot: u32,
+        group: u64,
+        max: usize,
+    ) -> Result<Vec<V>> {
+        field::get_group_array(self, slot, group, max)
+    }
+}
+
+/// Suspend callbacks for the given slots of a container, or every slot if none are given.
+fn suspend(handle: xhandle, slots: &mut [u32]) -> Result<()> {
+    let status =
+        unsafe { rxegy_sys::xcSuspendCallbacks(handle, slot_ptr(slots), slots.len() as u32) };
+
+    Success::try_from(status)?;
+    Ok(())
+}
+
+/// Resume callbacks for the given slots of a container, or every slot if none are given.
+fn resume(handle: xhandle, slots: &mut [u32]) -> Result<()> {
+    let status =
+        unsafe { rxegy_sys::xcResumeCallbacks(handle, slot_ptr(slots), slots.len() as u32) };
+
+    Success::try_from(status)?;
+    Ok(())
+}
+
+/// XCAPI expects a null slot array, rather than an empty one, to mean every slot.
+fn slot_ptr(slots: &mut [u32]) -> *mut u32 {
+    if slots.is_empty() {
+        ptr::null_mut()
+    } else {
+        slots.as_mut_ptr()
+    }
+}
+
+/// Callbacks suspended on one or more containers, which are resumed when the guard is dropped.
+///
+/// A guard taken on a whole session pins each container in the session's registry, so a container
+/// dropped while suspended is not destroyed (and its handle cannot be reused) until it is resumed.
+#[derive(Debug)]
+#[must_use = "callbacks are resumed as soon as the guard is dropped"]
+pub struct SuspendGuard<'a> {
+    handles: Vec<xhandle>,
+    registry: Option<Arc<Registry>>,
+    _object: PhantomData<&'a ()>,
+}
+
+impl SuspendGuard<'_> {
+    /// Create a guard for a container it borrows, which has not suspended anything yet.
+    pub(crate) fn new() -> Self {
+        Self {
+            handles: Vec::new(),
+            registry: None,
+            _object: PhantomData,
+        }
+    }
+
+    /// Create a guard which pins the containers it suspends in the given registry.
+    pub(crate) fn pinned(registry: Arc<Registry>) -> Self {
+        Self {
+            handles: Vec::new(),
+            registry: Some(registry),
+            _object: PhantomData,
+        }
+    }
+
+    /// Suspend every slot of the given container, and resume it when the guard is dropped.
+    ///
+    /// A container which has already been destroyed is skipped.
+    pub(crate) fn suspend(&mut self, handle: xhandle) -> Result<()> {
+        if !self.pin(handle) {
+            return Ok(());
+        }
+
+        if let Err(e) = suspend(handle, &mut []) {
+            self.unpin(handle);
+            return Err(e);
+        }
+
+        self.handles.push(handle);
+        Ok(())
+    }
+
+    /// Resume the given containers when the guard is dropped, without suspending them.
+    pub(crate) fn extend(&mut self, handles: impl IntoIterator<Item = xhandle>) {
+        for handle in handles {
+            if self.pin(handle) {
+                self.handles.push(handle);
+            }
+        }
+    }
+
+    fn pin(&self, handle: xhandle) -> bool {
+        self.registry
+            .as_ref()
+            .is_none_or(|registry| registry.pin(handle))
+    }
+
+    fn unpin(&self, handle: xhandle) {
+        if let Some(registry) = &self.registry {
+            registry.unpin(handle);
+        }
+    }
+
+    /// Resume callbacks, returning the first error encountered.
+    pub fn resume(mut self) -> Result<()> {
+        self.resume_all()
+    }
+
+    fn resume_all(&mut self) -> Result<()> {
+        let mut result = Ok(());
+
+        for handle in mem::take(&mut self.handles) {
+            let resumed = resume(handle, &mut []);
+
+            // The container may have been dropped while suspended, and is destroyed here if so
+            self.unpin(handle);
+
+            if let Err(e) = resumed {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl Drop for SuspendGuard<'_> {
+    fn drop(&mut self) {
+        if let Err(e) = self.resume_all() {
+            tracing::error!("Could not resume callbacks: {}", e);
+        }
+    }
+}
+
+/// The lifecycle state of a single slot within a container.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum SlotState {
+    /// Nothing has been requested on the slot, or it has been cleared.
+    Empty,
+    /// An item has been requested on the slot, but no data events have been received yet.
+    Pending,
+    /// The slot is receiving data events.
+    Active,
+}
+
+impl SlotState {
+    /// Query the state of a slot within the container with the given handle.
+    fn of(handle: xhandle, slot: u32) -> Result<Self> {
+        let mut flag = 0;
+
+        Success::try_from(unsafe { rxegy_sys::xcIsEmpty(handle, slot, &mut flag) })?;
+        if flag as u32 == rxegy_sys::XTRUE {
+            return Ok(Self::Empty);
+        }
+
+        Success::try_from(unsafe { rxegy_sys::xcIsPending(handle, slot, &mut flag) })?;
+        if flag as u32 == rxegy_sys::XTRUE {
+            return Ok(Self::Pending);
+        }
+
+        Success::try_from(unsafe { rxegy_sys::xcIsActive(handle, slot, &mut flag) })?;
+        if flag as u32 == rxegy_sys::XTRUE {
+            Ok(Self::Active)
+        } else {
+            Err(Error::SlotStateUnknown)
+        }
+    }
+}
+
+/// An iterator over the pending and active slots of a container.
+///
+/// Slots which change state while iterating may or may not be returned.
+#[derive(Debug)]
+pub struct Slots<'a> {
+    handle: xhandle,
+    next: u32,
+    count: u32,
+    _container: PhantomData<&'a ()>,
+}
+
+impl Iterator for Slots<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.count {
+            let slot = self.next;
+            self.next += 1;
+
+            match SlotState::of(self.handle, slot) {
+                Ok(SlotState::Empty) => {}
+                Ok(_) => return Some(slot),
+                Err(e) => {
+                    tracing::warn!(slot, "Could not read slot state: {}", e);
+                }
+            }
+        }
+
+        None
+    }
 }
 
 /// A trait used to retrieve the turnkey fro a container
 pub(crate) trait InnerCommon: Wrapper {
     /// The turnkey value set on the container's creation.
     fn turnkey(&self) -> Result<u64>;
+
+    /// The turnkey given when the slot was requested.
+    fn slot_turnkey(&self, slot: u32) -> Result<u64>;
+
+    /// The non-zero turnkeys of every pending or active slot.
+    fn slot_turnkeys(&self) -> Vec<u64>;
 }
 
 impl<T: RealTime> InnerCommon for T {
     fn turnkey(&self) -> Result<u64> {
         field::get_u64(self, rxegy_sys::XC_CONTAINER, RealTimeField::Turnkey)
     }
+
+    fn slot_turnkey(&self, slot: u32) -> Result<u64> {
+        let mut context = unsafe { mem::zeroed::<rxegy_sys::XC_SLOT_CONTEXT>() };
+        let status = unsafe { rxegy_sys::xcSlotContext(self.as_xhandle(), slot, &mut context) };
+        Success::try_from(status)?;
+        Ok(context.xcreq_turnkey)
+    }
+
+    fn slot_turnkeys(&self) -> Vec<u64> {
+        self.slots()
+            .filter_map(|slot| self.slot_turnkey(slot).ok())
+            .filter(|turnkey| *turnkey != 0)
+            .collect()
+    }
+}
+
+/// A function which destroys a container and releases the memory it was given.
+pub(crate) type Teardown = unsafe fn(xhandle);
+
+/// The set of containers created on a session, which are still alive.
+///
+/// Each owned container keeps its session alive, so the session is normally destroyed after every
+/// container. Any container still registered when it is, such as one whose owner was leaked, is
+/// torn down first, since XCAPI invalidates a session's containers (but not the contexts we gave
+/// them) when the session is destroyed.
+#[derive(Debug)]
+pub(crate) struct Registry(Mutex<Option<HashMap<usize, Entry>>>);
+
+/// A registered container, along with the user data retired from its slots.
+struct Entry {
+    teardown: Teardown,
+    /// User data which a callback may still be borrowing, freed once the container is destroyed.
+    retired: Vec<Box<dyn Send>>,
+    /// The number of suspend guards which must resume the container before it is destroyed.
+    pins: usize,
+    /// Whether the owner was dropped while the container was pinned.
+    released: bool,
+}
+
+impl Entry {
+    /// Destroy the container, then free its retired user data.
+    unsafe fn teardown(self, handle: usize) {
+        unsafe { (self.teardown)(handle as xhandle) };
+    }
+}
+
+impl Debug for Entry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Entry")
+            .field("retired", &self.retired.len())
+            .field("pins", &self.pins)
+            .field("released", &self.released)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Registry {
+    /// Create a new, empty registry for a live session.
+    pub(crate) fn new() -> Self {
+        Self(Mutex::new(Some(HashMap::new())))
+    }
+
+    /// Register a newly created container, returning the owner which will destroy it.
+    pub(crate) fn register(
+        self: &Arc<Self>,
+        handle: xhandle,
+        teardown: Teardown,
+        session: Arc<Lifeline>,
+    ) -> Result<Owner> {
+        self.0
+            .lock()
+            .map_err(|_e| Error::SessionPanic)?
+            .as_mut()
+            .ok_or(Error::SessionNotInitialized)?
+            .insert(
+                handle as usize,
+                Entry {
+                    teardown,
+                    retired: Vec::new(),
+                    pins: 0,
+                    released: false,
+                },
+            );
+
+        Ok(Owner {
+            registry: Arc::clone(self),
+            handle: handle as usize,
+            _session: session,
+        })
+    }
+
+    /// The handles of every container still registered.
+    pub(crate) fn handles(&self) -> Vec<xhandle> {
+        match self.0.lock() {
+            Ok(guard) => guard.iter().flatten().map(|(h, _)| *h as xhandle).collect(),
+            Err(poisoned) => poisoned
+                .into_inner()
+                .iter()
+                .flatten()
+                .map(|(h, _)| *h as xhandle)
+                .collect(),
+        }
+    }
+
+    /// Destroy every container still registered, and refuse any further registrations.
+    pub(crate) fn teardown_all(&self) {
+        let containers = match self.0.lock() {
+            Ok(mut guard) => guard.take(),
+            Err(poisoned) => poisoned.into_inner().take(),
+        };
+
+        for (handle, entry) in containers.into_iter().flatten() {
+            unsafe { entry.teardown(handle) };
+        }
+    }
+
+    /// Destroy a single container, if it hasn't already been torn down with its session.
+    ///
+    /// A pinned container is destroyed once the last suspend guard pinning it resumes it.
+    fn release(&self, handle: usize) {
+        let entry = {
+            let mut guard = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+            let Some(map) = guard.as_mut() else {
+                return;
+            };
+
+            match map.get_mut(&handle) {
+                Some(entry) if entry.pins > 0 => {
+                    entry.released = true;
+                    None
+                }
+                _ => map.remove(&handle),
+            }
+        };
+
+        if let Some(entry) = entry {
+            unsafe { entry.teardown(handle) };
+        }
+    }
+
+    /// Keep a container from being destroyed until it is unpinned, returning whether it is still
+    /// registered.
+    fn pin(&self, handle: xhandle) -> bool {
+        let mut guard = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        match guard
+            .as_mut()
+            .and_then(|map| map.get_mut(&(handle as usize)))
+        {
+            Some(entry) if !entry.released => {
+                entry.pins += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Release a pin, destroying the container if its owner was dropped while it was pinned.
+    fn unpin(&self, handle: xhandle) {
+        let handle = handle as usize;
+        let entry = {
+            let mut guard = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+            let Some(map) = guard.as_mut() else {
+                return;
+            };
+
+            match map.get_mut(&handle) {
+                Some(entry) => {
+                    entry.pins -= 1;
+                    if entry.pins == 0 && entry.released {
+                        map.remove(&handle)
+                    } else {
+                        None
+                    }
+                }
+                None => None,
+            }
+        };
+
+        if let Some(entry) = entry {
+            unsafe { entry.teardown(handle) };
+        }
+    }
+
+    /// Hold user data removed from one of a container's slots until the container is destroyed.
+    fn retire(&self, handle: usize, user_data: Box<dyn Send>) {
+        let mut guard = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        match guard.as_mut().and_then(|map| map.get_mut(&handle)) {
+            Some(entry) => entry.retired.push(user_data),
+            // The container is gone, so no callback can still be borrowing the user data
+            None => drop(user_data),
+        }
+    }
+}
+
+/// The ownership of a container created by one of this crate's builders.
+///
+/// Wrappers created inside callbacks have no owner, and dropping them does nothing.
+#[derive(Debug)]
+pub(crate) struct Owner {
+    registry: Arc<Registry>,
+    handle: usize,
+    /// Keeps the session alive until after the container is destroyed.
+    _session: Arc<Lifeline>,
+}
+
+impl Owner {
+    /// Destroy the owned container, unless its session already has.
+    pub(crate) fn release(self) {
+        self.registry.release(self.handle);
+    }
+
+    /// Free user data removed from one of the container's slots once the container is destroyed,
+    /// since a callback running on the XCAPI thread may still be borrowing it.
+    pub(crate) fn retire(&self, user_data: Box<dyn Send>) {
+        self.registry.retire(self.handle, user_data);
+    }
+}
+
+/// A type-erased container context, which dispatches raw XCAPI events to typed callbacks.
+///
+/// Containers are created with a leaked `Box<Box<dyn Dispatch>>` as their turnkey, so the
+/// (non-generic) XCAPI callback can find the handlers for its container without knowing the type
+/// of their user data.
+pub(crate) trait Dispatch {
+    /// Decode the given event and pass it, along with the slot's user data, to the handlers.
+    fn dispatch(
+        &self,
+        handle: xhandle,
+        slot: u32,
+        event_handle: xhandle,
+        event_type: u16,
+        turnkey: u64,
+        status: xerr,
+    ) -> Result<()>;
+
+    /// Deliver a vector of events received by a vectored container.
+    fn dispatch_vector(
+        &self,
+        handle: xhandle,
+        slot: u32,
+        turnkey: u64,
+        events: &[XC_EVENT_DESC],
+    ) -> Result<()> {
+        for desc in events {
+            self.dispatch(
+                handle,
+                slot,
+                desc.xvi_event,
+                desc.xvi_type,
+                turnkey,
+                desc.xvi_status,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Retrieve the context of the container with the given handle, if it was created by this crate.
+///
+/// # Safety
+///
+/// The container's turnkey must have been set to a leaked `Box<Box<dyn Dispatch>>`, which is
+/// valid for as long as the container can fire callbacks.
+pub(crate) unsafe fn context<'a>(handle: xhandle) -> Result<&'a dyn Dispatch> {
+    let mut turnkey = 0u64;
+    let status = unsafe {
+        rxegy_sys::xcGetField(
+            handle,
+            rxegy_sys::XC_CONTAINER,
+            RealTimeField::Turnkey.to_u64(),
+            &mut turnkey as *mut u64 as *mut _,
+            mem::size_of::<u64>() as u32,
+        )
+    };
+
+    Success::try_from(status)?;
+
+    let context = turnkey as *const Box<dyn Dispatch>;
+    unsafe { context.as_ref() }
+        .map(|context| &**context)
+        .ok_or(Error::NullObject)
+}
+
+/// A container whose context and slot user data were leaked from boxes by this crate.
+pub(crate) trait Owned: RealTime {
+    /// The type of the user data leaked into each slot's turnkey.
+    type UserData;
+}
+
+/// Destroy a container created by this crate, releasing its context and per-slot user data.
+///
+/// The turnkeys are collected before the container is destroyed, and freed only after XCAPI has
+/// finished delivering its outstanding callbacks. This must not be called from within a callback.
+pub(crate) unsafe fn teardown<T: Owned>(handle: xhandle) {
+    let container = match T::from_xhandle(handle) {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Could not tear down container: {}", e);
+            return;
+        }
+    };
+
+    let context = container.turnkey().unwrap_or_default();
+    let slots = container.slot_turnkeys();
+
+    let mut object = handle;
+    if let Err(e) = Success::try_from(unsafe { rxegy_sys::xcDestroyObject(&mut object) }) {
+        tracing::error!("Could not destroy container: {}", e);
+        // The callbacks may still be using our context, so leak it rather than free it.
+        return;
+    }
+
+    for turnkey in slots {
+        drop(unsafe { Box::from_raw(turnkey as *mut T::UserData) });
+    }
+
+    if context != 0 {
+        drop(unsafe { Box::from_raw(context as *mut Box<dyn Dispatch>) });
+    }
 }
 
 /// An enumeration of realtime fields.
@@ -1,7 +1,7 @@
 //! Container Callback Prototypes
 
 use crate::{
-    container::{EquityStream, KeylistCatalog},
+    container::{EquityEvent, EquityStream, KeylistCatalog},
     error::Result,
     event::{
         EquityCancel, EquityCorrection, EquityQuote, EquityRefresh, EquityTrade,
@@ -9,73 +9,191 @@ use crate::{
         OrderImbalance, Subscribe, TradeSummary, TradingAction,
     },
 };
-use std::any::Any;
 
 // EQUITY STREAM CALLBACKS
 
 /// The function prototype for a subscription callback on an equity stream.
-pub type EquityStreamSubscribeFn =
-    fn(stream: &EquityStream, event: &Subscribe, user_data: Option<&dyn Any>) -> Result<()>;
+pub type EquityStreamSubscribeFn<U = ()> =
+    fn(stream: &EquityStream<U>, event: &Subscribe, user_data: &U) -> Result<()>;
 
 /// The callback function prototype for a refresh event callback on an equity stream.
-pub type EquityStreamRefreshFn =
-    fn(stream: &EquityStream, event: &EquityRefresh, user_data: Option<&dyn Any>) -> Result<()>;
+pub type EquityStreamRefreshFn<U = ()> =
+    fn(stream: &EquityStream<U>, event: &EquityRefresh, user_data: &U) -> Result<()>;
 
 /// The callback function prototype for a trade event callback on an equity stream.
-pub type EquityStreamTradeFn =
-    fn(stream: &EquityStream, event: &EquityTrade, user_data: Option<&dyn Any>) -> Result<()>;
+pub type EquityStreamTradeFn<U = ()> =
+    fn(stream: &EquityStream<U>, event: &EquityTrade, user_data: &U) -> Result<()>;
 
 /// The callback function prototype for a quote event callback on an equity stream.
-pub type EquityStreamQuoteFn =
-    fn(stream: &EquityStream, event: &EquityQuote, user_data: Option<&dyn Any>) -> Result<()>;
+pub type EquityStreamQuoteFn<U = ()> =
+    fn(stream: &EquityStream<U>, event: &EquityQuote, user_data: &U) -> Result<()>;
 
 /// The callback function prototype for a cancel event callback on an equity stream.
-pub type EquityStreamCancelFn =
-    fn(stream: &EquityStream, event: &EquityCancel, user_data: Option<&dyn Any>) -> Result<()>;
+pub type EquityStreamCancelFn<U = ()> =
+    fn(stream: &EquityStream<U>, event: &EquityCancel, user_data: &U) -> Result<()>;
 
 /// The callback function prototype for a correction event callback on an equity stream.
-pub type EquityStreamCorrectionFn =
-    fn(stream: &EquityStream, event: &EquityCorrection, user_data: Option<&dyn Any>) -> Result<()>;
+pub type EquityStreamCorrectionFn<U = ()> =
+    fn(stream: &EquityStream<U>, event: &EquityCorrection, user_data: &U) -> Result<()>;
 
 /// The callback function prototype for a order imbalance callback on an equity stream.
-pub type EquityStreamOrderImbalanceFn =
-    fn(stream: &EquityStream, event: &OrderImbalance, user_data: Option<&dyn Any>) -> Result<()>;
+pub type EquityStreamOrderImbalanceFn<U = ()> =
+    fn(stream: &EquityStream<U>, event: &OrderImbalance, user_data: &U) -> Result<()>;
 
 /// The callback function prototype for a order imbalance callback on an equity stream.
-pub type EquityStreamTradingActionFn =
-    fn(stream: &EquityStream, event: &TradingAction, user_data: Option<&dyn Any>) -> Result<()>;
+pub type EquityStreamTradingActionFn<U = ()> =
+    fn(stream: &EquityStream<U>, event: &TradingAction, user_data: &U) -> Result<()>;
 
 /// The callback function prototype for a order imbalance callback on an equity stream.
-pub type EquityStreamIndicativePriceFn =
-    fn(stream: &EquityStream, event: &IndicativePrice, user_data: Option<&dyn Any>) -> Result<()>;
+pub type EquityStreamIndicativePriceFn<U = ()> =
+    fn(stream: &EquityStream<U>, event: &IndicativePrice, user_data: &U) -> Result<()>;
 
 /// The callback function prototype for a order imbalance callback on an equity stream.
-pub type EquityStreamTradeSummaryFn =
-    fn(stream: &EquityStream, event: &TradeSummary, user_data: Option<&dyn Any>) -> Result<()>;
+pub type EquityStreamTradeSummaryFn<U = ()> =
+    fn(stream: &EquityStream<U>, event: &TradeSummary, user_data: &U) -> Result<()>;
 
 /// The callback function prototype for a order imbalance callback on an equity stream.
-pub type EquityStreamExchangeStatisticsFn = fn(
-    stream: &EquityStream,
-    event: &ExchangeStatistics,
-    user_data: Option<&dyn Any>,
-) -> Result<()>;
+pub type EquityStreamExchangeStatisticsFn<U = ()> =
+    fn(stream: &EquityStream<U>, event: &ExchangeStatistics, user_data: &U) -> Result<()>;
+
+/// A handler for every kind of event on an equity stream.
+///
+/// Each method does nothing by default, so implementations only need to override the events they
+/// are interested in. Unlike the function prototypes above, a handler can hold its own state, which
+/// is only ever accessed by one callback at a time.
+pub trait EquityStreamHandler<U = ()>: Send + 'static {
+    /// Called when a subscription event occurs.
+    fn on_subscribe(
+        &mut self,
+        _stream: &EquityStream<U>,
+        _event: &Subscribe,
+        _user_data: &U,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when a refresh event is received.
+    fn on_refresh(
+        &mut self,
+        _stream: &EquityStream<U>,
+        _event: &EquityRefresh,
+        _user_data: &U,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when a trade event is received.
+    fn on_trade(
+        &mut self,
+        _stream: &EquityStream<U>,
+        _event: &EquityTrade,
+        _user_data: &U,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when a quote event is received.
+    fn on_quote(
+        &mut self,
+        _stream: &EquityStream<U>,
+        _event: &EquityQuote,
+        _user_data: &U,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when a trade cancellation event is received.
+    fn on_cancel(
+        &mut self,
+        _stream: &EquityStream<U>,
+        _event: &EquityCancel,
+        _user_data: &U,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when a trade correction event is received.
+    fn on_correction(
+        &mut self,
+        _stream: &EquityStream<U>,
+        _event: &EquityCorrection,
+        _user_data: &U,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when an order imbalance event is received.
+    fn on_order_imbalance(
+        &mut self,
+        _stream: &EquityStream<U>,
+        _event: &OrderImbalance,
+        _user_data: &U,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when a trading action event is received.
+    fn on_trading_action(
+        &mut self,
+        _stream: &EquityStream<U>,
+        _event: &TradingAction,
+        _user_data: &U,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when an indicative price event is received.
+    fn on_indicative_price(
+        &mut self,
+        _stream: &EquityStream<U>,
+        _event: &IndicativePrice,
+        _user_data: &U,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when a trade summary event is received.
+    fn on_trade_summary(
+        &mut self,
+        _stream: &EquityStream<U>,
+        _event: &TradeSummary,
+        _user_data: &U,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when an exchange statistics event is received.
+    fn on_exchange_statistics(
+        &mut self,
+        _stream: &EquityStream<U>,
+        _event: &ExchangeStatistics,
+        _user_data: &U,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called once per vector of events on a vectored stream, after each of them has been passed
+    /// to the per-event methods.
+    fn on_events(
+        &mut self,
+        _stream: &EquityStream<U>,
+        _events: &[EquityEvent],
+        _user_data: &U,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
 
 // KEYLIST CATALOG CALLBACKS
 
 /// A callback signature for subscription event handlers.
-pub type KeylistCatalogSubscribeFn =
-    fn(catalog: &KeylistCatalog, event: &Subscribe, user_data: Option<&dyn Any>) -> Result<()>;
+pub type KeylistCatalogSubscribeFn<U = ()> =
+    fn(catalog: &KeylistCatalog<U>, event: &Subscribe, user_data: &U) -> Result<()>;
 
 /// A callback signature for catalog refresh event handlers.
-pub type KeylistCatalogRefreshFn = fn(
-    catalog: &KeylistCatalog,
-    event: &KeylistCatalogRefresh,
-    user_data: Option<&dyn Any>,
-) -> Result<()>;
+pub type KeylistCatalogRefreshFn<U = ()> =
+    fn(catalog: &KeylistCatalog<U>, event: &KeylistCatalogRefresh, user_data: &U) -> Result<()>;
 
 /// A callback signature for catalog update event handlers.
-pub type KeylistCatalogUpdateFn = fn(
-    catalog: &KeylistCatalog,
-    event: &KeylistCatalogUpdate,
-    user_data: Option<&dyn Any>,
-) -> Result<()>;
+pub type KeylistCatalogUpdateFn<U = ()> =
+    fn(catalog: &KeylistCatalog<U>, event: &KeylistCatalogUpdate, user_data: &U) -> Result<()>;
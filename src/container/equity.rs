@@ -1,5 +1,15 @@
 //! Containers for use with top-of-book Equity and Equity Options data
 
-pub use self::stream::{Builder as StreamBuilder, Stream};
+#[cfg(feature = "async")]
+pub use self::events::{Events, OverflowPolicy};
+pub use self::{
+    owned::{EventHeader, OwnedEquityEvent},
+    ring::RingConsumer,
+    stream::{Builder as StreamBuilder, Event, Stream},
+};
 
+#[cfg(feature = "async")]
+mod events;
+mod owned;
+mod ring;
 mod stream;
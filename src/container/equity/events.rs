@@ -0,0 +1,170 @@
+//! An asynchronous stream of owned equity events
+
+use super::OwnedEquityEvent;
+use futures_core::Stream as FuturesStream;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Waker},
+};
+
+/// What to do with a new event when the channel to an [`Events`] stream is full.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum OverflowPolicy {
+    /// Block the XCAPI callback thread until the stream has room.
+    ///
+    /// This applies back-pressure to XCAPI, which will eventually cause the appliance to treat the
+    /// session as a slow consumer.
+    Block,
+    /// Discard the oldest queued event to make room for the new one.
+    #[default]
+    DropOldest,
+    /// Replace any queued event of the same kind for the same key with the new one, and otherwise
+    /// discard the oldest queued event to make room.
+    ConflatePerKey,
+}
+
+/// The state shared between the callback side and the stream side of the channel.
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    not_full: Condvar,
+    capacity: usize,
+    policy: OverflowPolicy,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    queue: VecDeque<OwnedEquityEvent>,
+    waker: Option<Waker>,
+    sender_closed: bool,
+    receiver_closed: bool,
+    dropped: u64,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        // Neither side panics while holding the lock, so this is never poisoned in practice
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The callback side of the channel, owned by the equity stream's builder.
+#[derive(Debug)]
+pub(crate) struct Sender(Arc<Shared>);
+
+impl Sender {
+    /// Queue an event according to the overflow policy, and wake the stream.
+    pub(crate) fn send(&self, event: OwnedEquityEvent) {
+        let mut state = self.0.lock();
+
+        if state.receiver_closed {
+            return;
+        }
+
+        if self.0.policy == OverflowPolicy::ConflatePerKey {
+            if let Some(queued) = state
+                .queue
+                .iter_mut()
+                .find(|queued| event.conflates(queued))
+            {
+                *queued = event;
+                state.dropped += 1;
+                return;
+            }
+        }
+
+        if state.queue.len() >= self.0.capacity {
+            match self.0.policy {
+                OverflowPolicy::Block => {
+                    state = self
+                        .0
+                        .not_full
+                        .wait_while(state, |state| {
+                            state.queue.len() >= self.0.capacity && !state.receiver_closed
+                        })
+                        .unwrap_or_else(PoisonError::into_inner);
+
+                    if state.receiver_closed {
+                        return;
+                    }
+                }
+                OverflowPolicy::DropOldest | OverflowPolicy::ConflatePerKey => {
+                    let _oldest = state.queue.pop_front();
+                    state.dropped += 1;
+                }
+            }
+        }
+
+        state.queue.push_back(event);
+
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl Drop for Sender {
+    fn drop(&mut self) {
+        let mut state = self.0.lock();
+        state.sender_closed = true;
+
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A [`futures_core::Stream`] of the events delivered to an equity stream.
+///
+/// The stream ends once the equity stream it was created for has been destroyed and every queued
+/// event has been consumed.
+#[derive(Debug)]
+pub struct Events(Arc<Shared>);
+
+impl Events {
+    /// The number of events which have been discarded or conflated away by the overflow policy.
+    pub fn dropped(&self) -> u64 {
+        self.0.lock().dropped
+    }
+}
+
+impl FuturesStream for Events {
+    type Item = OwnedEquityEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.0.lock();
+
+        if let Some(event) = state.queue.pop_front() {
+            drop(state);
+            self.0.not_full.notify_one();
+            Poll::Ready(Some(event))
+        } else if state.sender_closed {
+            Poll::Ready(None)
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for Events {
+    fn drop(&mut self) {
+        self.0.lock().receiver_closed = true;
+        self.0.not_full.notify_all();
+    }
+}
+
+/// Create a new bounded channel with the given capacity and overflow policy.
+pub(crate) fn channel(capacity: usize, policy: OverflowPolicy) -> (Sender, Events) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State::default()),
+        not_full: Condvar::new(),
+        capacity: capacity.max(1),
+        policy,
+    });
+
+    (Sender(Arc::clone(&shared)), Events(shared))
+}
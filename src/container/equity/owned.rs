@@ -0,0 +1,149 @@
+//! Owned copies of equity stream events
+
+use super::stream::Event;
+use crate::{
+    HiTime, Key,
+    error::{ExegyError, Result, Success},
+    event::{
+        Common, EquityCancelData, EquityCorrectionData, EquityQuoteData, EquityRefreshData,
+        EquityTradeData,
+    },
+};
+use rxegy_sys::xerr;
+
+/// The fields common to every owned equity event.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct EventHeader {
+    /// The key of the instrument the event refers to.
+    pub key: Key,
+    /// The error the event carried, if any.
+    pub error: Option<ExegyError>,
+    /// The line the event was received from, if the event has one.
+    pub line_id: Option<u16>,
+    /// The exchange sequence number of the event, if the event has one.
+    pub exchange_sequence: Option<u64>,
+    /// The time the event was received by the appliance, if the event has one.
+    pub receive_time: Option<HiTime>,
+}
+
+impl EventHeader {
+    /// Copy the common fields out of an event object, taking its error from the callback status.
+    fn read<E: Common>(event: &E, status: xerr) -> Result<Self> {
+        Ok(Self {
+            key: event.item_key()?,
+            error: Success::try_from(status).err(),
+            line_id: event.line_id().ok(),
+            exchange_sequence: event.exchange_sequence().ok(),
+            receive_time: event.receive_time().ok().map(HiTime::from),
+        })
+    }
+}
+
+/// An owned copy of an equity stream event, which may outlive the callback it was received in.
+///
+/// Trades, quotes, cancels, corrections and refreshes carry a snapshot of their fields. Every other
+/// variant carries only the [`EventHeader`]; its fields must be read from the stream within a
+/// callback.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum OwnedEquityEvent {
+    /// A subscription event, carrying only its header.
+    Subscribe(EventHeader),
+    /// A trade cancellation event, and a snapshot of its fields.
+    Cancel(EventHeader, EquityCancelData),
+    /// A trade correction event, and a snapshot of its fields.
+    Correction(EventHeader, EquityCorrectionData),
+    /// A quote event, and a snapshot of its fields.
+    Quote(EventHeader, EquityQuoteData),
+    /// A refresh event, and a snapshot of the instrument's image.
+    Refresh(EventHeader, EquityRefreshData),
+    /// A trade event, and a snapshot of its fields.
+    Trade(EventHeader, EquityTradeData),
+    /// An exchange statistics event, carrying only its header.
+    ExchangeStatistics(EventHeader),
+    /// An indicative price event, carrying only its header.
+    IndicativePrice(EventHeader),
+    /// An order imbalance event, carrying only its header.
+    OrderImbalance(EventHeader),
+    /// A trade summary event, carrying only its header.
+    TradeSummary(EventHeader),
+    /// A trading action event, carrying only its header.
+    TradingAction(EventHeader),
+}
+
+impl OwnedEquityEvent {
+    /// Retrieve the fields common to every event.
+    pub fn header(&self) -> &EventHeader {
+        match self {
+            Self::Subscribe(header)
+            | Self::Cancel(header, _)
+            | Self::Correction(header, _)
+            | Self::Quote(header, _)
+            | Self::Refresh(header, _)
+            | Self::Trade(header, _)
+            | Self::ExchangeStatistics(header)
+            | Self::IndicativePrice(header)
+            | Self::OrderImbalance(header)
+            | Self::TradeSummary(header)
+            | Self::TradingAction(header) => header,
+        }
+    }
+
+    /// Copy an event received by a callback, so it may outlive the callback.
+    ///
+    /// The error in the header is taken from the `status` the callback was given. The fields of
+    /// trades, quotes, cancels, corrections and refreshes are read with a single field group
+    /// retrieval, and their header is filled in from it and the item key, rather than being read
+    /// field by field.
+    pub fn snapshot(event: &Event, status: xerr) -> Result<Self> {
+        macro_rules! data_header {
+            ($evt:expr, $data:expr) => {
+                EventHeader {
+                    key: $evt.item_key()?,
+                    error: Success::try_from(status).err(),
+                    line_id: Some($data.line_id),
+                    exchange_sequence: Some($data.sequence),
+                    receive_time: Some($data.receive_time),
+                }
+            };
+        }
+
+        Ok(match event {
+            Event::Cancel(evt) => {
+                let data = evt.to_owned()?;
+                Self::Cancel(data_header!(evt, data), data)
+            }
+            Event::Correction(evt) => {
+                let data = evt.to_owned()?;
+                Self::Correction(data_header!(evt, data), data)
+            }
+            Event::Quote(evt) => {
+                let data = evt.to_owned()?;
+                Self::Quote(data_header!(evt, data), data)
+            }
+            Event::Refresh(evt) => {
+                let data = evt.to_owned()?;
+                Self::Refresh(data_header!(evt, data), data)
+            }
+            Event::Trade(evt) => {
+                let data = evt.to_owned()?;
+                Self::Trade(data_header!(evt, data), data)
+            }
+            Event::Subscribe(evt) => Self::Subscribe(EventHeader::read(evt, status)?),
+            Event::ExchangeStatistics(evt) => {
+                Self::ExchangeStatistics(EventHeader::read(evt, status)?)
+            }
+            Event::IndicativePrice(evt) => Self::IndicativePrice(EventHeader::read(evt, status)?),
+            Event::OrderImbalance(evt) => Self::OrderImbalance(EventHeader::read(evt, status)?),
+            Event::TradeSummary(evt) => Self::TradeSummary(EventHeader::read(evt, status)?),
+            Event::TradingAction(evt) => Self::TradingAction(EventHeader::read(evt, status)?),
+        })
+    }
+
+    /// Whether this event should replace the given queued event when conflating.
+    #[cfg(feature = "async")]
+    pub(crate) fn conflates(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+            && self.header().key == other.header().key
+    }
+}
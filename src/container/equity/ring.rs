@@ -0,0 +1,309 @@
+//! A lock-free, single-producer single-consumer ring buffer of owned equity events
+
+use super::OwnedEquityEvent;
+use std::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    },
+};
+
+/// Keep the producer and consumer indices on separate cache lines.
+#[derive(Debug, Default)]
+#[repr(align(64))]
+struct Padded<T>(T);
+
+/// The state shared between the callback side and the polling side of the ring.
+#[derive(Debug)]
+struct Shared {
+    /// The index of the next slot the consumer will read, only written by the consumer.
+    head: Padded<AtomicUsize>,
+    /// The index of the next slot the producer will write, only written by the producer.
+    tail: Padded<AtomicUsize>,
+    overruns: AtomicU64,
+    dropped: AtomicU64,
+    producer_closed: AtomicBool,
+    consumer_closed: AtomicBool,
+    mask: usize,
+    slots: Box<[UnsafeCell<MaybeUninit<OwnedEquityEvent>>]>,
+}
+
+// Each slot is only ever accessed by one side at a time, as handed over by the head and tail indices
+unsafe impl Sync for Shared {}
+
+impl Shared {
+    fn slot(&self, index: usize) -> *mut MaybeUninit<OwnedEquityEvent> {
+        self.slots[index & self.mask].get()
+    }
+}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        let tail = *self.tail.0.get_mut();
+        let mut head = *self.head.0.get_mut();
+
+        while head != tail {
+            unsafe { (*self.slot(head)).assume_init_drop() };
+            head = head.wrapping_add(1);
+        }
+    }
+}
+
+/// The callback side of the ring, owned by the equity stream's builder.
+#[derive(Debug)]
+pub(crate) struct Producer(Arc<Shared>);
+
+impl Producer {
+    /// Copy an event into the ring, counting it as an overrun if the ring is full.
+    pub(crate) fn push(&self, event: OwnedEquityEvent) {
+        if self.0.consumer_closed.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let tail = self.0.tail.0.load(Ordering::Relaxed);
+        let head = self.0.head.0.load(Ordering::Acquire);
+
+        if tail.wrapping_sub(head) > self.0.mask {
+            self.0.overruns.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        unsafe { (*self.0.slot(tail)).write(event) };
+        self.0.tail.0.store(tail.wrapping_add(1), Ordering::Release);
+    }
+
+    /// Count an event which could not be copied out of its callback.
+    pub(crate) fn drop_event(&self) {
+        self.0.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Drop for Producer {
+    fn drop(&mut self) {
+        self.0.producer_closed.store(true, Ordering::Release);
+    }
+}
+
+/// The polling side of a ring buffer of owned copies of equity stream events.
+///
+/// Polling never blocks or takes a lock, so a consumer will typically spin on [`Self::pop()`] from a
+/// dedicated thread.
+#[derive(Debug)]
+pub struct RingConsumer(Arc<Shared>);
+
+impl RingConsumer {
+    /// Take the oldest event out of the ring, if there is one.
+    pub fn pop(&mut self) -> Option<OwnedEquityEvent> {
+        let head = self.0.head.0.load(Ordering::Relaxed);
+        let tail = self.0.tail.0.load(Ordering::Acquire);
+
+        if head == tail {
+            return None;
+        }
+
+        let event = unsafe { (*self.0.slot(head)).assume_init_read() };
+        self.0.head.0.store(head.wrapping_add(1), Ordering::Release);
+
+        Some(event)
+    }
+
+    /// The number of events currently waiting in the ring.
+    pub fn len(&self) -> usize {
+        let tail = self.0.tail.0.load(Ordering::Acquire);
+        let head = self.0.head.0.load(Ordering::Relaxed);
+
+        tail.wrapping_sub(head)
+    }
+
+    /// Whether there are no events waiting in the ring.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The maximum number of events the ring can hold.
+    pub fn capacity(&self) -> usize {
+        self.0.slots.len()
+    }
+
+    /// The number of events which were discarded because the ring was full when they arrived.
+    pub fn overruns(&self) -> u64 {
+        self.0.overruns.load(Ordering::Relaxed)
+    }
+
+    /// The number of events which were discarded because they could not be copied out of their
+    /// callback.
+    pub fn dropped(&self) -> u64 {
+        self.0.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Whether the equity stream feeding this ring has been destroyed.
+    ///
+    /// Events which were queued before the stream was destroyed may still be popped.
+    pub fn is_closed(&self) -> bool {
+        self.0.producer_closed.load(Ordering::Acquire)
+    }
+}
+
+impl Drop for RingConsumer {
+    fn drop(&mut self) {
+        self.0.consumer_closed.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Create a new ring which holds at least `capacity` events, rounded up to a power of two.
+pub(crate) fn ring(capacity: usize) -> (Producer, RingConsumer) {
+    let capacity = capacity.max(1).next_power_of_two();
+    let shared = Arc::new(Shared {
+        head: Padded::default(),
+        tail: Padded::default(),
+        overruns: AtomicU64::new(0),
+        dropped: AtomicU64::new(0),
+        producer_closed: AtomicBool::new(false),
+        consumer_closed: AtomicBool::new(false),
+        mask: capacity - 1,
+        slots: (0..capacity)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect(),
+    });
+
+    (Producer(Arc::clone(&shared)), RingConsumer(shared))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Key, container::EquityEventHeader};
+    use rxegy_sys::XC_KEY;
+    use std::thread;
+
+    fn event(sequence: u64) -> OwnedEquityEvent {
+        OwnedEquityEvent::Subscribe(EquityEventHeader {
+            key: Key::new(XC_KEY::default()),
+            error: None,
+            line_id: None,
+            exchange_sequence: Some(sequence),
+            receive_time: None,
+        })
+    }
+
+    fn sequence(event: &OwnedEquityEvent) -> u64 {
+        event
+            .header()
+            .exchange_sequence
+            .expect("Test events have a sequence")
+    }
+
+    #[test]
+    fn capacity_rounds_up() {
+        assert_eq!(ring(0).1.capacity(), 1);
+        assert_eq!(ring(3).1.capacity(), 4);
+        assert_eq!(ring(8).1.capacity(), 8);
+    }
+
+    #[test]
+    fn empty() {
+        let (_producer, mut consumer) = ring(4);
+
+        assert!(consumer.is_empty());
+        assert!(consumer.pop().is_none());
+        assert_eq!(consumer.overruns(), 0);
+    }
+
+    #[test]
+    fn full() {
+        let (producer, mut consumer) = ring(4);
+
+        for seq in 0..6 {
+            producer.push(event(seq));
+        }
+
+        assert_eq!(consumer.len(), 4);
+        assert_eq!(consumer.overruns(), 2);
+
+        let popped = std::iter::from_fn(|| consumer.pop())
+            .map(|event| sequence(&event))
+            .collect::<Vec<_>>();
+        assert_eq!(popped, [0, 1, 2, 3]);
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn wraparound() {
+        let (producer, mut consumer) = ring(4);
+
+        for seq in 0..64 {
+            producer.push(event(seq));
+            producer.push(event(seq + 1000));
+            assert_eq!(consumer.len(), 2);
+            assert_eq!(consumer.pop().map(|event| sequence(&event)), Some(seq));
+            assert_eq!(
+                consumer.pop().map(|event| sequence(&event)),
+                Some(seq + 1000)
+            );
+        }
+
+        assert!(consumer.pop().is_none());
+        assert_eq!(consumer.overruns(), 0);
+    }
+
+    #[test]
+    fn drops_unread_events() {
+        let (producer, consumer) = ring(4);
+        let shared = Arc::downgrade(&consumer.0);
+
+        producer.push(event(0));
+        producer.push(event(1));
+        drop(consumer);
+        producer.push(event(2));
+        drop(producer);
+
+        assert!(shared.upgrade().is_none());
+    }
+
+    #[test]
+    fn closed() {
+        let (producer, consumer) = ring(4);
+
+        assert!(!consumer.is_closed());
+        drop(producer);
+        assert!(consumer.is_closed());
+    }
+
+    #[test]
+    fn cross_thread() {
+        const COUNT: u64 = 100_000;
+
+        let (producer, mut consumer) = ring(16);
+
+        let thread = thread::spawn(move || {
+            for seq in 0..COUNT {
+                // Retry whatever the ring had no room for, so every event arrives in order
+                loop {
+                    let overruns = producer.0.overruns.load(Ordering::Relaxed);
+                    producer.push(event(seq));
+                    if producer.0.overruns.load(Ordering::Relaxed) == overruns {
+                        break;
+                    }
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let mut expected = 0;
+        while expected < COUNT {
+            match consumer.pop() {
+                Some(event) => {
+                    assert_eq!(sequence(&event), expected);
+                    expected += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+
+        thread.join().expect("Producer thread panicked");
+        assert!(consumer.is_closed());
+        assert!(consumer.pop().is_none());
+    }
+}
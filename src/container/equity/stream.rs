@@ -1,15 +1,16 @@
 //! Top-of-book Containers for Equity and Equity Options Streams
 
+#[cfg(feature = "async")]
+use super::events::{self, Events, OverflowPolicy, Sender};
+use super::{
+    OwnedEquityEvent,
+    ring::{self, Producer, RingConsumer},
+};
 use crate::{
     AlternateId, Error, FeedId, GroupId, HiTime, InstrumentStatus, Key, MarketStatus, TradeVenue,
     container::{
-        InnerCommon, RealTime,
-        callbacks::{
-            EquityStreamCancelFn, EquityStreamCorrectionFn, EquityStreamExchangeStatisticsFn,
-            EquityStreamIndicativePriceFn, EquityStreamOrderImbalanceFn, EquityStreamQuoteFn,
-            EquityStreamRefreshFn, EquityStreamSubscribeFn, EquityStreamTradeFn,
-            EquityStreamTradeSummaryFn, EquityStreamTradingActionFn,
-        },
+        self, Common, Dispatch, InnerCommon, Owned, Owner, RealTime, SlotState,
+        callbacks::EquityStreamHandler,
     },
     error::{Result, Success},
     event::{
@@ -18,28 +19,56 @@ use crate::{
         TradingAction,
     },
     field::{self, Field as FieldTrait},
-    impl_wrapper_on_newtype,
-    misc::OrderRefIdKind,
+    impl_wrapper_on_owned_newtype,
     object::{Kind as ObjectKind, Wrapper},
+    order_ref::OrderRefIdKind,
     session::TickerSession,
 };
-use rxegy_sys::{xerr, xhandle};
+use rxegy_sys::{XC_EVENT_DESC, XC_SLOT_CONTEXT, xerr, xhandle};
 use std::{
-    any::Any,
     ffi::{CString, c_void},
-    panic, process,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    marker::PhantomData,
+    mem, panic, process,
     ptr::{self, NonNull},
+    slice,
+    sync::{Mutex, PoisonError},
 };
 
-/// An equity stream container.
-#[derive(Debug)]
-pub struct Stream(NonNull<c_void>);
+/// The maximum number of contributing markets read from a per-market status array.
+const MAX_CONTRIBUTING_MARKETS: usize = 64;
 
-impl_wrapper_on_newtype!(Stream, ObjectKind::RealtimeEquityStream);
+/// An equity stream container, whose slots each hold user data of type `U`.
+///
+/// Dropping a stream returned by [`Builder::build`] destroys the container, waiting for any
+/// outstanding callbacks to complete, and releases the builder and per-slot user data. As a result,
+/// a stream may not be dropped from within one of its own callbacks. The session the stream was
+/// built on is kept alive until then.
+pub struct Stream<U = ()>(NonNull<c_void>, Option<Owner>, PhantomData<U>);
 
-impl RealTime for Stream {}
+impl_wrapper_on_owned_newtype!(Stream, ObjectKind::RealtimeEquityStream);
 
-impl Stream {
+impl<U: 'static> RealTime for Stream<U> {}
+
+impl<U: 'static> Owned for Stream<U> {
+    type UserData = U;
+}
+
+impl<U> Debug for Stream<U> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_tuple("Stream").field(&self.0).finish()
+    }
+}
+
+impl<U> Drop for Stream<U> {
+    fn drop(&mut self) {
+        if let Some(owner) = self.1.take() {
+            owner.release();
+        }
+    }
+}
+
+impl<U: Send + 'static> Stream<U> {
     /// Retrieve the maximum rate, in quotes per second, for "metered quote delivery" to
     /// subscriptions for this object.
     pub fn quote_rate(&self) -> Result<u32> {
@@ -164,6 +193,46 @@ impl Stream {
         field::get_xc_trading_state(self, slot, Field::MarketStatus).map(MarketStatus::new)
     }
 
+    /// Retrieve the normalized trading status/substatus reported by each contributing market, along
+    /// with the feed of that market.
+    ///
+    /// Equity streams have no field naming the feed which reported each status, so the feed comes
+    /// from the subscribed key. When that is a single market, it is the only contributor. When it is
+    /// an Exegy-generated composite (see [`FeedId::is_composite()`]), the contributing feeds are not
+    /// published, and are given as `None`.
+    pub fn instrument_status_by_market(
+        &self,
+        slot: u32,
+    ) -> Result<Vec<(Option<FeedId>, InstrumentStatus)>> {
+        let feed = self.key(slot)?.feed_id().contributor();
+        Ok(field::get_array(
+            self,
+            slot,
+            Field::InstrumentStatus,
+            MAX_CONTRIBUTING_MARKETS,
+        )?
+        .into_iter()
+        .map(|status| (feed, InstrumentStatus::new(status)))
+        .collect())
+    }
+
+    /// Retrieve the normalized trading status/substatus reported by each contributing market for
+    /// the market or instrument group, along with the feed of that market.
+    ///
+    /// Feeds are given as in [`Self::instrument_status_by_market()`].
+    pub fn market_status_by_market(
+        &self,
+        slot: u32,
+    ) -> Result<Vec<(Option<FeedId>, MarketStatus)>> {
+        let feed = self.key(slot)?.feed_id().contributor();
+        Ok(
+            field::get_array(self, slot, Field::MarketStatus, MAX_CONTRIBUTING_MARKETS)?
+                .into_iter()
+                .map(|status| (feed, MarketStatus::new(status)))
+                .collect(),
+        )
+    }
+
     /// Retrieve the prime "exchange" (i.e., listing exchange feed) for the instrument.
     pub fn prime_feed_id(&self, slot: u32) -> Result<FeedId> {
         field::get_xc_exchange_id(self, slot, Field::PrimeExch).map(FeedId::new)
@@ -195,11 +264,10 @@ impl Stream {
     ///
     /// This function wraps [xcRequestItemByString](rxegy_sys::xcRequestItemByString) and exhibits
     /// similar semantics and restrictions.
-    pub fn subscribe_by_string(&self, key_string: &str, user_data: Box<dyn Any>) -> Result<u32> {
+    pub fn subscribe_by_string(&self, key_string: &str, user_data: U) -> Result<u32> {
         let key_string = CString::new(key_string)?;
 
-        let thin_ptr = Box::new(user_data);
-        let turnkey = Box::into_raw(thin_ptr) as u64;
+        let turnkey = Box::into_raw(Box::new(user_data)) as u64;
 
         let mut slot = rxegy_sys::XC_NEXT_AVAILABLE_SLOT;
 
@@ -212,29 +280,287 @@ impl Stream {
             )
         };
 
-        Success::try_from(status)?;
+        if let Err(e) = Success::try_from(status) {
+            // No callbacks will be fired for this request, so don't leak the user data
+            let _user_data = unsafe { Box::from_raw(turnkey as *mut U) };
+            return Err(e.into());
+        }
 
         Ok(slot)
     }
+
+    /// Subscribe to every key in the given list with a single request, returning the slot or error
+    /// for each key, in order.
+    ///
+    /// This wraps [xcRequestItems](rxegy_sys::xcRequestItems), which places the keys in a
+    /// contiguous block of empty slots starting at the lowest-numbered empty slot, so the call as a
+    /// whole will fail if that block is not free. The `user_data` function is called once for each
+    /// key to create the data handed to callbacks for that slot. Keys with symbols too long to fit
+    /// an [`XC_KEY`](rxegy_sys::XC_KEY) must be subscribed by string instead.
+    ///
+    /// A slot whose state cannot be read after the request is assumed to be subscribed, so that it
+    /// can still be unsubscribed.
+    ///
+    /// Note that [xcSubmitItems](rxegy_sys::xcSubmitItems) only applies to write containers, so it
+    /// cannot be used to subscribe a stream.
+    pub fn subscribe_keys<F>(&self, keys: &[Key], mut user_data: F) -> Result<Vec<Result<u32>>>
+    where
+        F: FnMut(&Key) -> U,
+    {
+        let mut contexts = keys
+            .iter()
+            .map(|key| XC_SLOT_CONTEXT {
+                xcreq_key: key.inner(),
+                xcreq_turnkey: Box::into_raw(Box::new(user_data(key))) as u64,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        let mut slot = rxegy_sys::XC_NEXT_AVAILABLE_SLOT;
+
+        let status = unsafe {
+            rxegy_sys::xcRequestItems(
+                self.as_xhandle(),
+                contexts.as_mut_ptr(),
+                contexts.len() as u32,
+                &mut slot,
+            )
+        };
+
+        if let Err(e) = Success::try_from(status) {
+            // No callbacks will be fired for any of the items, so don't leak the user data
+            for context in contexts {
+                let _user_data = unsafe { Box::from_raw(context.xcreq_turnkey as *mut U) };
+            }
+            return Err(e.into());
+        }
+
+        // XCAPI reports success if any item was requested, so check each slot individually
+        Ok(contexts
+            .into_iter()
+            .zip(slot..)
+            .map(|(context, slot)| match self.slot_state(slot) {
+                Ok(SlotState::Empty) => {
+                    let turnkey = context.xcreq_turnkey;
+                    let _user_data = unsafe { Box::from_raw(turnkey as *mut U) };
+                    Err(Error::ItemNotRequested)
+                }
+                Ok(_) => Ok(slot),
+                // The item may well be live, and its user data must outlive its callbacks, so
+                // leave the slot to the caller to unsubscribe or clear like any other
+                Err(e) => {
+                    tracing::warn!(slot, "Could not read requested slot state: {}", e);
+                    Ok(slot)
+                }
+            })
+            .collect())
+    }
+
+    /// Subscribe to every key string in the given list, returning the slot or error for each key
+    /// string, in order.
+    ///
+    /// Key strings may contain symbols which are too long for an [`XC_KEY`](rxegy_sys::XC_KEY), so
+    /// each one is requested individually via [`Stream::subscribe_by_string()`]. The `user_data`
+    /// function is called once for each key string to create the data handed to callbacks for that
+    /// slot.
+    pub fn subscribe_by_strings<I, S, F>(
+        &self,
+        key_strings: I,
+        mut user_data: F,
+    ) -> Vec<Result<u32>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        F: FnMut(&str) -> U,
+    {
+        key_strings
+            .into_iter()
+            .map(|key_string| {
+                let key_string = key_string.as_ref();
+                self.subscribe_by_string(key_string, user_data(key_string))
+            })
+            .collect()
+    }
+
+    /// Unsubscribe the given slot, and release the user data it was subscribed with.
+    ///
+    /// This wraps [xcClearSlots](rxegy_sys::xcClearSlots), so no further callbacks will be fired
+    /// for the slot, and it is immediately available for reuse. (The `xcDeleteItem*` functions
+    /// only apply to read-write containers.) It may not be called from within a callback.
+    ///
+    /// A callback already running for the slot may still be borrowing its user data, so the user
+    /// data is dropped when the stream is, rather than immediately. Unsubscribing a slot which is
+    /// already empty returns [`Error::SlotEmpty`].
+    pub fn unsubscribe(&self, slot: u32) -> Result<()> {
+        let turnkey = occupied_turnkey(self.slot_state(slot)?, || self.slot_turnkey(slot))?;
+        let mut slots = [slot];
+
+        let status = unsafe {
+            rxegy_sys::xcClearSlots(self.as_xhandle(), slots.as_mut_ptr(), slots.len() as u32)
+        };
+
+        Success::try_from(status)?;
+
+        self.retire(turnkey);
+
+        Ok(())
+    }
+
+    /// Unsubscribe every slot in this stream, and release their user data.
+    ///
+    /// Like [`Stream::unsubscribe()`], this may not be called from within a callback, and the user
+    /// data is dropped when the stream is.
+    pub fn clear(&self) -> Result<()> {
+        let turnkeys = self.slot_turnkeys();
+
+        let status = unsafe { rxegy_sys::xcClearSlots(self.as_xhandle(), ptr::null_mut(), 0) };
+
+        Success::try_from(status)?;
+
+        for turnkey in turnkeys {
+            self.retire(turnkey);
+        }
+
+        Ok(())
+    }
+
+    /// Hand the user data of a cleared slot to the owner, to be dropped with the stream.
+    fn retire(&self, turnkey: u64) {
+        let user_data = unsafe { Box::from_raw(turnkey as *mut U) };
+
+        match &self.1 {
+            Some(owner) => owner.retire(user_data),
+            // Wrappers without an owner only exist within callbacks, where clearing is not allowed
+            None => mem::forget(user_data),
+        }
+    }
 }
 
+/// The turnkey of a slot which may be cleared, or an error if the slot holds no user data.
+///
+/// The turnkey is only read once the slot is known not to be empty.
+fn occupied_turnkey(state: SlotState, turnkey: impl FnOnce() -> Result<u64>) -> Result<u64> {
+    if state == SlotState::Empty {
+        return Err(Error::SlotEmpty);
+    }
+
+    match turnkey()? {
+        0 => Err(Error::SlotEmpty),
+        turnkey => Ok(turnkey),
+    }
+}
+
+/// A boxed callback for a single kind of equity stream event.
+type Callback<U, E> = Box<dyn FnMut(&Stream<U>, &E, &U) -> Result<()> + Send>;
+
 /// A builder which can create an equity stream container
-#[derive(Debug, Default)]
-pub struct Builder {
-    subscribe: Option<EquityStreamSubscribeFn>,
-    refresh: Option<EquityStreamRefreshFn>,
-    trade: Option<EquityStreamTradeFn>,
-    quote: Option<EquityStreamQuoteFn>,
-    cancel: Option<EquityStreamCancelFn>,
-    correction: Option<EquityStreamCorrectionFn>,
-    order_imbalance: Option<EquityStreamOrderImbalanceFn>,
-    trading_action: Option<EquityStreamTradingActionFn>,
-    indicative_price: Option<EquityStreamIndicativePriceFn>,
-    trade_summary: Option<EquityStreamTradeSummaryFn>,
-    exchange_statistics: Option<EquityStreamExchangeStatisticsFn>,
+///
+/// Events are delivered to the [`EquityStreamHandler`] set with [`Builder::handler()`], if any,
+/// and then to the callback set for that kind of event, if any. Callbacks may be closures or plain
+/// functions, such as the prototypes in [`callbacks`](crate::container::callbacks).
+pub struct Builder<U = ()> {
+    #[cfg(feature = "async")]
+    events: Option<Sender>,
+    ring: Option<Producer>,
+    vectored: bool,
+    handler: Option<Box<dyn EquityStreamHandler<U>>>,
+    subscribe: Option<Callback<U, Subscribe>>,
+    refresh: Option<Callback<U, EquityRefresh>>,
+    trade: Option<Callback<U, EquityTrade>>,
+    quote: Option<Callback<U, EquityQuote>>,
+    cancel: Option<Callback<U, EquityCancel>>,
+    correction: Option<Callback<U, EquityCorrection>>,
+    order_imbalance: Option<Callback<U, OrderImbalance>>,
+    trading_action: Option<Callback<U, TradingAction>>,
+    indicative_price: Option<Callback<U, IndicativePrice>>,
+    trade_summary: Option<Callback<U, TradeSummary>>,
+    exchange_statistics: Option<Callback<U, ExchangeStatistics>>,
+    events_vector: Option<Callback<U, [Event]>>,
 }
 
-impl Builder {
+impl<U> Default for Builder<U> {
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "async")]
+            events: None,
+            ring: None,
+            vectored: false,
+            handler: None,
+            subscribe: None,
+            refresh: None,
+            trade: None,
+            quote: None,
+            cancel: None,
+            correction: None,
+            order_imbalance: None,
+            trading_action: None,
+            indicative_price: None,
+            trade_summary: None,
+            exchange_statistics: None,
+            events_vector: None,
+        }
+    }
+}
+
+impl<U> Debug for Builder<U> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut debug = f.debug_struct("Builder");
+        #[cfg(feature = "async")]
+        debug.field("events", &self.events.is_some());
+        debug
+            .field("ring", &self.ring.is_some())
+            .field("vectored", &self.vectored)
+            .field("handler", &self.handler.is_some())
+            .field("subscribe", &self.subscribe.is_some())
+            .field("refresh", &self.refresh.is_some())
+            .field("trade", &self.trade.is_some())
+            .field("quote", &self.quote.is_some())
+            .field("cancel", &self.cancel.is_some())
+            .field("correction", &self.correction.is_some())
+            .field("order_imbalance", &self.order_imbalance.is_some())
+            .field("trading_action", &self.trading_action.is_some())
+            .field("indicative_price", &self.indicative_price.is_some())
+            .field("trade_summary", &self.trade_summary.is_some())
+            .field("exchange_statistics", &self.exchange_statistics.is_some())
+            .field("events_vector", &self.events_vector.is_some())
+            .finish()
+    }
+}
+
+impl<U: Send + 'static> Builder<U> {
+    /// Copy every event on the equity stream into a preallocated, lock-free ring buffer.
+    ///
+    /// The ring holds at least `capacity` events, rounded up to a power of two. The callback thread
+    /// never waits for the consumer: an event which arrives while the ring is full is discarded and
+    /// counted in [`RingConsumer::overruns()`]. Events are copied before being delivered to any
+    /// handler or callbacks, and without taking a lock. If neither is set, the callback does nothing
+    /// else, so a hot-path consumer should set neither, and pin the callback thread
+    /// with [`Builder::callback_affinity()`](crate::session::Builder::callback_affinity)
+    /// and [`Builder::callback_priority()`](crate::session::Builder::callback_priority).
+    pub fn ring_buffer(mut self, capacity: usize) -> (Self, RingConsumer) {
+        let (producer, consumer) = ring::ring(capacity);
+        self.ring = Some(producer);
+        (self, consumer)
+    }
+
+    /// Create an asynchronous stream of owned copies of every event on the equity stream.
+    ///
+    /// Events are queued in a channel which holds at most `capacity` events, and the given policy
+    /// decides what happens when a new event arrives while it is full. Events are queued before
+    /// being delivered to any handler or callbacks.
+    #[cfg(feature = "async")]
+    pub fn events(mut self, capacity: usize, policy: OverflowPolicy) -> (Self, Events) {
+        let (sender, events) = events::channel(capacity, policy);
+        self.events = Some(sender);
+        (self, events)
+    }
+
+    /// Set the handler which will receive every event on the stream.
+    pub fn handler(mut self, handler: impl EquityStreamHandler<U>) -> Self {
+        self.handler = Some(Box::new(handler));
+        self
+    }
+
     /// Set the callback to be fired when a subscription event occurs.
     ///
     /// Fires when a subscription or retrieval request is made by the client application. Note that
@@ -246,8 +572,11 @@ impl Builder {
     /// instrument and the subscription request is fulfilled. In addition, note that the second
     /// subscribe event may have a status of [ExegyError::Access](crate::ExegyError::Access),
     /// instead of [Success::Generic], indicating that the user is not authorized for the requested data.
-    pub fn on_subscribe(mut self, func: EquityStreamSubscribeFn) -> Self {
-        self.subscribe = Some(func);
+    pub fn on_subscribe<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&Stream<U>, &Subscribe, &U) -> Result<()> + Send + 'static,
+    {
+        self.subscribe = Some(Box::new(func));
         self
     }
 
@@ -261,8 +590,11 @@ impl Builder {
     /// - At start of day to populate the initial data image
     /// - During a disconnect/reconnect scenario (as XCAPI automatically repopulates previously
     ///   existing subscriptions).
-    pub fn on_refresh(mut self, func: EquityStreamRefreshFn) -> Self {
-        self.refresh = Some(func);
+    pub fn on_refresh<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&Stream<U>, &EquityRefresh, &U) -> Result<()> + Send + 'static,
+    {
+        self.refresh = Some(Box::new(func));
         self
     }
 
@@ -270,8 +602,11 @@ impl Builder {
     ///
     /// The callback will be fired for each slot currently subscribed to the relevant equity
     /// instrument.
-    pub fn on_trade(mut self, func: EquityStreamTradeFn) -> Self {
-        self.trade = Some(func);
+    pub fn on_trade<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&Stream<U>, &EquityTrade, &U) -> Result<()> + Send + 'static,
+    {
+        self.trade = Some(Box::new(func));
         self
     }
 
@@ -279,8 +614,11 @@ impl Builder {
     ///
     /// The callback will be fired for each slot currently subscribed to the relevant equity
     /// instrument.
-    pub fn on_quote(mut self, func: EquityStreamQuoteFn) -> Self {
-        self.quote = Some(func);
+    pub fn on_quote<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&Stream<U>, &EquityQuote, &U) -> Result<()> + Send + 'static,
+    {
+        self.quote = Some(Box::new(func));
         self
     }
 
@@ -288,8 +626,11 @@ impl Builder {
     ///
     /// The callback will be fired for each slot currently subscribed to the relevant equity
     /// instrument.
-    pub fn on_cancel(mut self, func: EquityStreamCancelFn) -> Self {
-        self.cancel = Some(func);
+    pub fn on_cancel<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&Stream<U>, &EquityCancel, &U) -> Result<()> + Send + 'static,
+    {
+        self.cancel = Some(Box::new(func));
         self
     }
 
@@ -297,8 +638,11 @@ impl Builder {
     ///
     /// The callback will be fired for each slot currently subscribed to the relevant equity
     /// instrument.
-    pub fn on_correction(mut self, func: EquityStreamCorrectionFn) -> Self {
-        self.correction = Some(func);
+    pub fn on_correction<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&Stream<U>, &EquityCorrection, &U) -> Result<()> + Send + 'static,
+    {
+        self.correction = Some(Box::new(func));
         self
     }
 
@@ -307,8 +651,11 @@ impl Builder {
     ///
     /// The callback will be fired for each slot currently subscribed to the relevant equity
     /// instrument.
-    pub fn on_order_imbalance(mut self, func: EquityStreamOrderImbalanceFn) -> Self {
-        self.order_imbalance = Some(func);
+    pub fn on_order_imbalance<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&Stream<U>, &OrderImbalance, &U) -> Result<()> + Send + 'static,
+    {
+        self.order_imbalance = Some(Box::new(func));
         self
     }
 
@@ -318,8 +665,11 @@ impl Builder {
     ///
     /// The callback will be fired for each slot currently subscribed to the relevant equity
     /// instrument.
-    pub fn on_trading_action(mut self, func: EquityStreamTradingActionFn) -> Self {
-        self.trading_action = Some(func);
+    pub fn on_trading_action<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&Stream<U>, &TradingAction, &U) -> Result<()> + Send + 'static,
+    {
+        self.trading_action = Some(Box::new(func));
         self
     }
 
@@ -328,14 +678,20 @@ impl Builder {
     ///
     /// The [IndicativePrice::indicative_price_kind] method will  indicates which type of price
     /// (opening, closing, or settlement) is being provided.
-    pub fn on_indicative_price(mut self, func: EquityStreamIndicativePriceFn) -> Self {
-        self.indicative_price = Some(func);
+    pub fn on_indicative_price<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&Stream<U>, &IndicativePrice, &U) -> Result<()> + Send + 'static,
+    {
+        self.indicative_price = Some(Box::new(func));
         self
     }
 
     /// Sets the callback to be fired when a trade summary event occurs.
-    pub fn on_trade_summary(mut self, func: EquityStreamTradeSummaryFn) -> Self {
-        self.trade_summary = Some(func);
+    pub fn on_trade_summary<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&Stream<U>, &TradeSummary, &U) -> Result<()> + Send + 'static,
+    {
+        self.trade_summary = Some(Box::new(func));
         self
     }
 
@@ -344,140 +700,195 @@ impl Builder {
     ///
     /// This information may include VWAP, trade count, turnover, and short sale statistics when
     /// these are provided by the feed.
-    pub fn on_exchange_statistics(mut self, func: EquityStreamExchangeStatisticsFn) -> Self {
-        self.exchange_statistics = Some(func);
+    pub fn on_exchange_statistics<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&Stream<U>, &ExchangeStatistics, &U) -> Result<()> + Send + 'static,
+    {
+        self.exchange_statistics = Some(Box::new(func));
+        self
+    }
+
+    /// Create the stream in vectored mode, where XCAPI delivers events in vectors, such as every
+    /// event decoded from a single packet.
+    ///
+    /// Each event in a vector is still passed to the handler and per-event callbacks in turn, after
+    /// which the handler's [`EquityStreamHandler::on_events()`] method and the
+    /// [`Self::on_events()`] callback receive the whole vector at once.
+    pub fn vectored(mut self) -> Self {
+        self.vectored = true;
+        self
+    }
+
+    /// Set the callback to be fired once per vector of events, and create the stream in vectored
+    /// mode.
+    ///
+    /// This allows a burst of trades and quotes to be processed together, for example to compute a
+    /// conflated BBO once per packet rather than once per event.
+    pub fn on_events<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&Stream<U>, &[Event], &U) -> Result<()> + Send + 'static,
+    {
+        self.events_vector = Some(Box::new(func));
+        self.vectored = true;
         self
     }
 
     /// Build a new session.
-    pub fn build(self, session: &TickerSession, max_slots: u32) -> Result<Stream> {
+    pub fn build(self, session: &TickerSession, max_slots: u32) -> Result<Stream<U>> {
         let mut object = ptr::null_mut();
 
-        let context = Box::new(Box::new(self) as Box<dyn Any>);
+        let vectored = self.vectored;
+        let context = Box::new(Box::new(Context::new(self)) as Box<dyn Dispatch>);
         let turnkey = Box::into_raw(context) as u64;
 
-        let status = unsafe {
-            rxegy_sys::xcCreateContainer(
-                session.as_xhandle(),
-                <Stream as Wrapper>::KIND as u16,
-                &mut object,
-                Some(_rxegy_equity_stream_callback),
-                turnkey,
-                max_slots,
-            )
+        let status = if vectored {
+            unsafe {
+                rxegy_sys::xcCreateContainerWithVectoredEvents(
+                    session.as_xhandle(),
+                    <Stream<U> as Wrapper>::KIND as u16,
+                    &mut object,
+                    Some(_rxegy_equity_stream_vectored_callback),
+                    turnkey,
+                    max_slots,
+                )
+            }
+        } else {
+            unsafe {
+                rxegy_sys::xcCreateContainer(
+                    session.as_xhandle(),
+                    <Stream<U> as Wrapper>::KIND as u16,
+                    &mut object,
+                    Some(_rxegy_equity_stream_callback),
+                    turnkey,
+                    max_slots,
+                )
+            }
         };
 
-        Success::try_from(status)?;
+        if let Err(e) = Success::try_from(status) {
+            // Don't leak the builder if we failed to create the stream
+            let _context = unsafe { Box::from_raw(turnkey as *mut Box<dyn Dispatch>) };
+            return Err(e.into());
+        }
 
-        Stream::from_xhandle(object)
+        let teardown = container::teardown::<Stream<U>>;
+        let owner = match session.register(object, teardown) {
+            Ok(owner) => owner,
+            Err(e) => {
+                unsafe { teardown(object) };
+                return Err(e);
+            }
+        };
+
+        let mut stream = Stream::<U>::from_xhandle(object)?;
+        stream.1 = Some(owner);
+
+        Ok(stream)
     }
 
     fn dispatch(
-        &self,
-        stream: &Stream,
+        &mut self,
+        stream: &Stream<U>,
         _slot: u32,
-        event: Event,
-        user_data: Option<&Box<dyn Any>>,
-        _status: xerr,
+        event: &Event,
+        user_data: &U,
+        #[cfg_attr(not(feature = "async"), allow(unused_variables))] status: xerr,
     ) -> Result<()> {
+        #[cfg(feature = "async")]
+        if let Some(sender) = &self.events {
+            match OwnedEquityEvent::snapshot(event, status) {
+                Ok(owned) => sender.send(owned),
+                Err(e) => tracing::warn!("Could not copy equity stream event: {}", e),
+            }
+        }
+
         match event {
             Event::Subscribe(event) => {
-                if let Some(func) = self.subscribe {
-                    if let Some(user_data) = user_data {
-                        func(stream, &event, Some(user_data.as_ref()))?;
-                    } else {
-                        func(stream, &event, None)?;
-                    }
+                if let Some(handler) = &mut self.handler {
+                    handler.on_subscribe(stream, event, user_data)?;
+                }
+                if let Some(func) = &mut self.subscribe {
+                    func(stream, event, user_data)?;
                 }
             }
             Event::Cancel(event) => {
-                if let Some(func) = self.cancel {
-                    if let Some(user_data) = user_data {
-                        func(stream, &event, Some(user_data.as_ref()))?;
-                    } else {
-                        func(stream, &event, None)?;
-                    }
+                if let Some(handler) = &mut self.handler {
+                    handler.on_cancel(stream, event, user_data)?;
+                }
+                if let Some(func) = &mut self.cancel {
+                    func(stream, event, user_data)?;
                 }
             }
             Event::Correction(event) => {
-                if let Some(func) = self.correction {
-                    if let Some(user_data) = user_data {
-                        func(stream, &event, Some(user_data.as_ref()))?;
-                    } else {
-                        func(stream, &event, None)?;
-                    }
+                if let Some(handler) = &mut self.handler {
+                    handler.on_correction(stream, event, user_data)?;
+                }
+                if let Some(func) = &mut self.correction {
+                    func(stream, event, user_data)?;
                 }
             }
             Event::Quote(event) => {
-                if let Some(func) = self.quote {
-                    if let Some(user_data) = user_data {
-                        func(stream, &event, Some(user_data.as_ref()))?;
-                    } else {
-                        func(stream, &event, None)?;
-                    }
+                if let Some(handler) = &mut self.handler {
+                    handler.on_quote(stream, event, user_data)?;
+                }
+                if let Some(func) = &mut self.quote {
+                    func(stream, event, user_data)?;
                 }
             }
             Event::Refresh(event) => {
-                if let Some(func) = self.refresh {
-                    if let Some(user_data) = user_data {
-                        func(stream, &event, Some(user_data.as_ref()))?;
-                    } else {
-                        func(stream, &event, None)?;
-                    }
+                if let Some(handler) = &mut self.handler {
+                    handler.on_refresh(stream, event, user_data)?;
+                }
+                if let Some(func) = &mut self.refresh {
+                    func(stream, event, user_data)?;
                 }
             }
             Event::Trade(event) => {
-                if let Some(func) = self.trade {
-                    if let Some(user_data) = user_data {
-                        func(stream, &event, Some(user_data.as_ref()))?;
-                    } else {
-                        func(stream, &event, None)?;
-                    }
+                if let Some(handler) = &mut self.handler {
+                    handler.on_trade(stream, event, user_data)?;
+                }
+                if let Some(func) = &mut self.trade {
+                    func(stream, event, user_data)?;
                 }
             }
             Event::ExchangeStatistics(event) => {
-                if let Some(func) = self.exchange_statistics {
-                    if let Some(user_data) = user_data {
-                        func(stream, &event, Some(user_data.as_ref()))?;
-                    } else {
-                        func(stream, &event, None)?;
-                    }
+                if let Some(handler) = &mut self.handler {
+                    handler.on_exchange_statistics(stream, event, user_data)?;
+                }
+                if let Some(func) = &mut self.exchange_statistics {
+                    func(stream, event, user_data)?;
                 }
             }
             Event::IndicativePrice(event) => {
-                if let Some(func) = self.indicative_price {
-                    if let Some(user_data) = user_data {
-                        func(stream, &event, Some(user_data.as_ref()))?;
-                    } else {
-                        func(stream, &event, None)?;
-                    }
+                if let Some(handler) = &mut self.handler {
+                    handler.on_indicative_price(stream, event, user_data)?;
+                }
+                if let Some(func) = &mut self.indicative_price {
+                    func(stream, event, user_data)?;
                 }
             }
             Event::OrderImbalance(event) => {
-                if let Some(func) = self.order_imbalance {
-                    if let Some(user_data) = user_data {
-                        func(stream, &event, Some(user_data.as_ref()))?;
-                    } else {
-                        func(stream, &event, None)?;
-                    }
+                if let Some(handler) = &mut self.handler {
+                    handler.on_order_imbalance(stream, event, user_data)?;
+                }
+                if let Some(func) = &mut self.order_imbalance {
+                    func(stream, event, user_data)?;
                 }
             }
             Event::TradeSummary(event) => {
-                if let Some(func) = self.trade_summary {
-                    if let Some(user_data) = user_data {
-                        func(stream, &event, Some(user_data.as_ref()))?;
-                    } else {
-                        func(stream, &event, None)?;
-                    }
+                if let Some(handler) = &mut self.handler {
+                    handler.on_trade_summary(stream, event, user_data)?;
+                }
+                if let Some(func) = &mut self.trade_summary {
+                    func(stream, event, user_data)?;
                 }
             }
             Event::TradingAction(event) => {
-                if let Some(func) = self.trading_action {
-                    if let Some(user_data) = user_data {
-                        func(stream, &event, Some(user_data.as_ref()))?;
-                    } else {
-                        func(stream, &event, None)?;
-                    }
+                if let Some(handler) = &mut self.handler {
+                    handler.on_trading_action(stream, event, user_data)?;
+                }
+                if let Some(func) = &mut self.trading_action {
+                    func(stream, event, user_data)?;
                 }
             }
         }
@@ -486,7 +897,147 @@ impl Builder {
     }
 }
 
-type Context = Builder;
+/// The context given to an equity stream container.
+///
+/// Events are copied into the ring buffer, if any, without taking a lock. The builder is only
+/// locked if it has a handler, callbacks or an asynchronous stream to deliver events to.
+struct Context<U> {
+    ring: Option<Producer>,
+    delivers: bool,
+    builder: Mutex<Builder<U>>,
+}
+
+impl<U> Context<U> {
+    fn new(mut builder: Builder<U>) -> Self {
+        let ring = builder.ring.take();
+        #[cfg(feature = "async")]
+        let events = builder.events.is_some();
+        #[cfg(not(feature = "async"))]
+        let events = false;
+        let delivers = events
+            || builder.handler.is_some()
+            || builder.subscribe.is_some()
+            || builder.refresh.is_some()
+            || builder.trade.is_some()
+            || builder.quote.is_some()
+            || builder.cancel.is_some()
+            || builder.correction.is_some()
+            || builder.order_imbalance.is_some()
+            || builder.trading_action.is_some()
+            || builder.indicative_price.is_some()
+            || builder.trade_summary.is_some()
+            || builder.exchange_statistics.is_some()
+            || builder.events_vector.is_some();
+
+        Self {
+            ring,
+            delivers,
+            builder: Mutex::new(builder),
+        }
+    }
+
+    /// Copy an event into the ring buffer, if there is one.
+    fn push(&self, event: &Event, status: xerr) {
+        if let Some(producer) = &self.ring {
+            match OwnedEquityEvent::snapshot(event, status) {
+                Ok(owned) => producer.push(owned),
+                Err(_) => producer.drop_event(),
+            }
+        }
+    }
+}
+
+impl<U: Send + 'static> Dispatch for Context<U> {
+    fn dispatch(
+        &self,
+        handle: xhandle,
+        slot: u32,
+        event_handle: xhandle,
+        event_type: u16,
+        turnkey: u64,
+        status: xerr,
+    ) -> Result<()> {
+        let event = Event::from_xhandle_and_type(event_handle, event_type)?;
+        self.push(&event, status);
+
+        if !self.delivers {
+            return Ok(());
+        }
+
+        let stream = Stream::<U>::from_xhandle(handle)?;
+
+        // SAFETY: every slot's turnkey is set to a leaked box of our user data type
+        let user_data = unsafe { (turnkey as *const U).as_ref() }.ok_or(Error::NullObject)?;
+
+        // Panics abort the process, so the lock is never poisoned in practice
+        self.builder
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .dispatch(&stream, slot, &event, user_data, status)
+    }
+
+    fn dispatch_vector(
+        &self,
+        handle: xhandle,
+        slot: u32,
+        turnkey: u64,
+        events: &[XC_EVENT_DESC],
+    ) -> Result<()> {
+        let stream = Stream::<U>::from_xhandle(handle)?;
+
+        // SAFETY: every slot's turnkey is set to a leaked box of our user data type
+        let user_data = unsafe { (turnkey as *const U).as_ref() }.ok_or(Error::NullObject)?;
+
+        // Panics abort the process, so the lock is never poisoned in practice
+        let mut builder = self
+            .delivers
+            .then(|| self.builder.lock().unwrap_or_else(PoisonError::into_inner));
+
+        let mut vector = Vec::with_capacity(if self.delivers { events.len() } else { 0 });
+        for desc in events {
+            let (event_handle, event_type, status) =
+                (desc.xvi_event, desc.xvi_type, desc.xvi_status);
+            let event = match Event::from_xhandle_and_type(event_handle, event_type) {
+                Ok(event) => event,
+                Err(e) => {
+                    tracing::warn!(
+                        exegy.event.type = event_type,
+                        "Skipping unknown equity stream event: {}",
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            self.push(&event, status);
+
+            if let Some(builder) = &mut builder {
+                if let Err(e) = builder.dispatch(&stream, slot, &event, user_data, status) {
+                    tracing::error!(
+                        exegy.event.type = event_type,
+                        "Could not dispatch equity stream event: {}",
+                        e
+                    );
+                }
+
+                vector.push(event);
+            }
+        }
+
+        let Some(mut builder) = builder else {
+            return Ok(());
+        };
+
+        if let Some(handler) = &mut builder.handler {
+            handler.on_events(&stream, &vector, user_data)?;
+        }
+        if let Some(func) = &mut builder.events_vector {
+            func(&stream, &vector, user_data)?;
+        }
+
+        Ok(())
+    }
+}
 
 #[unsafe(no_mangle)]
 unsafe extern "C" fn _rxegy_equity_stream_callback(
@@ -500,67 +1051,62 @@ unsafe extern "C" fn _rxegy_equity_stream_callback(
     if let Err(_e) = panic::catch_unwind(|| {
         tracing::trace_span!("_rxegy_equity_stream_callback");
 
-        let stream = match Stream::from_xhandle(handle) {
-            Ok(s) => s,
+        // SAFETY: equity streams are only created by the builder, which sets the turnkey
+        let context = match unsafe { container::context(handle) } {
+            Ok(ctx) => ctx,
             Err(e) => {
-                tracing::error!(
-                    "Could not find equity stream object in callback handle: {}",
-                    e
-                );
+                tracing::error!("Could not retrieve equity stream context: {}", e);
                 return;
             }
         };
 
-        let event = match Event::from_xhandle_and_type(event_handle, event_type) {
-            Ok(evt) => evt,
-            Err(e) => {
-                tracing::error!(exegy.event.type = event_type, "Unexpected event type in equity stream callback: {}", e);
-                return;
-            }
-        };
-
-        let stream_turnkey = match stream.turnkey() {
-            Ok(stk) => stk,
-            Err(e) => {
-                tracing::error!("Could not retrieve equity stream turnkey: {}", e);
-                return;
-            }
-        };
-
-        let context_thin_raw = stream_turnkey as *mut Box<dyn Any>;
-        if context_thin_raw.is_null() {
-            tracing::error!("Equity stream turnkey was null");
-            return;
+        if let Err(e) = context.dispatch(handle, slot, event_handle, event_type, turnkey, status) {
+            tracing::error!(
+                exegy.event.type = event_type,
+                "Could not dispatch equity stream event: {}",
+                e
+            );
         }
+    }) {
+        tracing::error!("Equity Stream callback panicked, aboring application");
+        process::abort();
+    }
+}
 
-        let context_thin = unsafe { Box::from_raw(context_thin_raw) };
-        let context = match (**context_thin).downcast_ref::<Context>() {
-            Some(ctx) => ctx,
-            None => {
-                tracing::error!("Could not downcast the equity stream context from a thin pointer");
-                let _leaked = Box::into_raw(context_thin);
+#[unsafe(no_mangle)]
+unsafe extern "C" fn _rxegy_equity_stream_vectored_callback(
+    handle: xhandle,
+    slot: u32,
+    turnkey: u64,
+    count: u32,
+    events: *const XC_EVENT_DESC,
+) {
+    if let Err(_e) = panic::catch_unwind(|| {
+        tracing::trace_span!("_rxegy_equity_stream_vectored_callback");
+
+        // SAFETY: equity streams are only created by the builder, which sets the turnkey
+        let context = match unsafe { container::context(handle) } {
+            Ok(ctx) => ctx,
+            Err(e) => {
+                tracing::error!("Could not retrieve equity stream context: {}", e);
                 return;
             }
         };
 
-        let user_data = if turnkey == 0 {
-            None
+        let events = if events.is_null() {
+            &[][..]
         } else {
-            let user_data_thin_raw = turnkey as *mut Box<dyn Any>;
-            Some(unsafe { Box::from_raw(user_data_thin_raw) })
+            // SAFETY: XCAPI passes an array of `count` descriptors, valid for this callback
+            unsafe { slice::from_raw_parts(events, count as usize) }
         };
 
-        if let Err(e) = context.dispatch(&stream, slot, event, user_data.as_deref(), status) {
-            tracing::error!("The callback returned an error: {}", e);
-        }
-
-        // Leak the user data pointer so it isn't freed
-        if let Some(user_data) = user_data {
-            let _leaked = Box::into_raw(user_data);
+        if let Err(e) = context.dispatch_vector(handle, slot, turnkey, events) {
+            tracing::error!(
+                exegy.event.count = count,
+                "Could not dispatch equity stream events: {}",
+                e
+            );
         }
-
-        // Leak the context pointer so it isn't freed
-        let _leaked = Box::into_raw(context_thin);
     }) {
         tracing::error!("Equity Stream callback panicked, aboring application");
         process::abort();
@@ -596,19 +1142,32 @@ impl FieldTrait for Field {
     }
 }
 
+/// An event delivered to an equity stream, which is only valid during the callback it was
+/// delivered in.
 #[derive(Debug)]
 #[repr(u16)]
 pub enum Event {
+    /// A subscription event.
     Subscribe(Subscribe) = Subscribe::KIND as u16,
+    /// A trade cancellation event.
     Cancel(EquityCancel) = EquityCancel::KIND as u16,
+    /// A trade correction event.
     Correction(EquityCorrection) = EquityCorrection::KIND as u16,
+    /// A quote event.
     Quote(EquityQuote) = EquityQuote::KIND as u16,
+    /// A refresh event.
     Refresh(EquityRefresh) = EquityRefresh::KIND as u16,
+    /// A trade event.
     Trade(EquityTrade) = EquityTrade::KIND as u16,
+    /// An exchange statistics event.
     ExchangeStatistics(ExchangeStatistics) = ExchangeStatistics::KIND as u16,
+    /// An indicative price event.
     IndicativePrice(IndicativePrice) = IndicativePrice::KIND as u16,
+    /// An order imbalance event.
     OrderImbalance(OrderImbalance) = OrderImbalance::KIND as u16,
+    /// A trade summary event.
     TradeSummary(TradeSummary) = TradeSummary::KIND as u16,
+    /// A trading action event.
     TradingAction(TradingAction) = TradingAction::KIND as u16,
 }
 
@@ -643,3 +1202,32 @@ impl Event {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unsubscribe_empty_slot() {
+        // The turnkey of an empty slot may be stale, so it must not be read at all
+        assert!(matches!(
+            occupied_turnkey(SlotState::Empty, || panic!("read an empty slot's turnkey")),
+            Err(Error::SlotEmpty)
+        ));
+        assert!(matches!(
+            occupied_turnkey(SlotState::Active, || Ok(0)),
+            Err(Error::SlotEmpty)
+        ));
+    }
+
+    #[test]
+    fn unsubscribe_occupied_slot() {
+        for state in [SlotState::Pending, SlotState::Active] {
+            assert_eq!(occupied_turnkey(state, || Ok(0x1000)).unwrap(), 0x1000);
+        }
+        assert!(matches!(
+            occupied_turnkey(SlotState::Active, || Err(Error::NullObject)),
+            Err(Error::NullObject)
+        ));
+    }
+}
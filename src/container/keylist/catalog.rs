@@ -2,80 +2,130 @@
 
 use crate::{
     container::{
-        InnerCommon, RealTime,
+        self, Dispatch, Owned, Owner, RealTime,
         callbacks::{KeylistCatalogRefreshFn, KeylistCatalogSubscribeFn, KeylistCatalogUpdateFn},
     },
     error::{Error, Result, Success},
     event::{KeylistCatalogRefresh, KeylistCatalogUpdate, Subscribe},
-    impl_wrapper_on_newtype,
+    impl_wrapper_on_owned_newtype,
     object::{Kind as ObjectKind, Wrapper},
     session::TickerSession,
 };
 use rxegy_sys::{xerr, xhandle};
 use std::{
-    any::Any,
+    cmp::Ordering,
     ffi::{CStr, c_void},
+    fmt::{Debug, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
     process,
     ptr::{self, NonNull},
 };
 
-/// An XCAPI object containing a keylist catalog
-#[derive(Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
-#[repr(transparent)]
-pub struct Catalog(NonNull<c_void>);
+/// An XCAPI object containing a keylist catalog, whose callbacks receive user data of type `U`
+///
+/// Dropping a catalog returned by [`Builder::build`] destroys the container and releases the
+/// builder and user data, so it may not be dropped from within one of its own callbacks. The
+/// session the catalog was built on is kept alive until then.
+pub struct Catalog<U = ()>(NonNull<c_void>, Option<Owner>, PhantomData<U>);
 
-impl_wrapper_on_newtype!(Catalog, ObjectKind::RealtimeKeylistCatalog);
+impl_wrapper_on_owned_newtype!(Catalog, ObjectKind::RealtimeKeylistCatalog);
 
-impl RealTime for Catalog {}
+impl<U: 'static> RealTime for Catalog<U> {}
+
+impl<U: 'static> Owned for Catalog<U> {
+    type UserData = U;
+}
+
+impl<U> Debug for Catalog<U> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_tuple("Catalog").field(&self.0).finish()
+    }
+}
+
+impl<U> Drop for Catalog<U> {
+    fn drop(&mut self) {
+        if let Some(owner) = self.1.take() {
+            owner.release();
+        }
+    }
+}
+
+impl<U> Eq for Catalog<U> {}
+
+impl<U> Hash for Catalog<U> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<U> Ord for Catalog<U> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<U> PartialEq for Catalog<U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<U> PartialOrd for Catalog<U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
 /// A builder for constructing a keylist catalog
-#[derive(Default)]
-pub struct Builder {
-    subscribe: Option<KeylistCatalogSubscribeFn>,
-    refresh: Option<KeylistCatalogRefreshFn>,
-    update: Option<KeylistCatalogUpdateFn>,
+pub struct Builder<U = ()> {
+    subscribe: Option<KeylistCatalogSubscribeFn<U>>,
+    refresh: Option<KeylistCatalogRefreshFn<U>>,
+    update: Option<KeylistCatalogUpdateFn<U>>,
+}
+
+impl<U> Default for Builder<U> {
+    fn default() -> Self {
+        Self {
+            subscribe: None,
+            refresh: None,
+            update: None,
+        }
+    }
 }
 
-impl Builder {
+impl<U: Send + 'static> Builder<U> {
     /// Set the callback to be fired when a subscribe event is received
-    pub fn on_subscribe(mut self, func: KeylistCatalogSubscribeFn) -> Self {
+    pub fn on_subscribe(mut self, func: KeylistCatalogSubscribeFn<U>) -> Self {
         self.subscribe = Some(func);
         self
     }
 
     /// Set the callback to be fired when a refresh event is received
-    pub fn on_refresh(mut self, func: KeylistCatalogRefreshFn) -> Self {
+    pub fn on_refresh(mut self, func: KeylistCatalogRefreshFn<U>) -> Self {
         self.refresh = Some(func);
         self
     }
 
     /// Set the callback to be fired when an update event is received
-    pub fn on_update(mut self, func: KeylistCatalogUpdateFn) -> Self {
+    pub fn on_update(mut self, func: KeylistCatalogUpdateFn<U>) -> Self {
         self.update = Some(func);
         self
     }
 
     /// Build a new keylist catalog using the given session, and supplying the given user data to callbacks.
-    pub fn build(
-        self,
-        session: &TickerSession,
-        user_data: Option<Box<dyn Any>>,
-    ) -> Result<Catalog> {
+    pub fn build(self, session: &TickerSession, user_data: U) -> Result<Catalog<U>> {
         let catalog = self.create_catalog(session)?;
 
-        if let Err(e) = Self::subscribe(&catalog, user_data) {
-            let mut object = catalog.as_xhandle();
-            let status = unsafe { rxegy_sys::xcDestroyObject(&mut object) };
-            Success::try_from(status)?;
-            return Err(e);
-        }
+        // Dropping the catalog on failure will destroy it
+        Self::subscribe(&catalog, user_data)?;
 
         Ok(catalog)
     }
 
     /// Create the catalog object, consuming ourseles in the process.
-    fn create_catalog(self, session: &TickerSession) -> Result<Catalog> {
-        let context = Box::new(self) as Box<dyn Any>;
+    fn create_catalog(self, session: &TickerSession) -> Result<Catalog<U>> {
+        let context = Box::new(self) as Box<dyn Dispatch>;
         let thin_ptr = Box::new(context);
         let turnkey = Box::into_raw(thin_ptr) as u64;
         let mut object = ptr::null_mut();
@@ -93,21 +143,28 @@ impl Builder {
 
         if let Err(e) = Success::try_from(status) {
             // Don't leak the builder if we failed to create the catalog
-            let _context = unsafe { Box::from_raw(turnkey as *mut Builder) };
+            let _context = unsafe { Box::from_raw(turnkey as *mut Box<dyn Dispatch>) };
             return Err(e.into());
         };
 
-        Catalog::from_xhandle(object)
+        let teardown = container::teardown::<Catalog<U>>;
+        let owner = match session.register(object, teardown) {
+            Ok(owner) => owner,
+            Err(e) => {
+                unsafe { teardown(object) };
+                return Err(e);
+            }
+        };
+
+        let mut catalog = Catalog::<U>::from_xhandle(object)?;
+        catalog.1 = Some(owner);
+
+        Ok(catalog)
     }
 
     /// Static helper function to subscribe to the empty string and start the catalog sync process.
-    fn subscribe(catalog: &Catalog, user_data: Option<Box<dyn Any>>) -> Result<()> {
-        let turnkey = if let Some(fat) = user_data {
-            let thin = Box::new(fat);
-            Box::into_raw(thin) as u64
-        } else {
-            0u64
-        };
+    fn subscribe(catalog: &Catalog<U>, user_data: U) -> Result<()> {
+        let turnkey = Box::into_raw(Box::new(user_data)) as u64;
 
         let mut slot = rxegy_sys::XC_NEXT_AVAILABLE_SLOT;
         let key_string = CStr::from_bytes_until_nul(b"")?;
@@ -121,7 +178,11 @@ impl Builder {
             )
         };
 
-        Success::try_from(status)?;
+        if let Err(e) = Success::try_from(status) {
+            // No callbacks will be fired for this request, so don't leak the user data
+            let _user_data = unsafe { Box::from_raw(turnkey as *mut U) };
+            return Err(e.into());
+        }
 
         Ok(())
     }
@@ -129,38 +190,26 @@ impl Builder {
     /// Dispatch the event
     fn dispatch(
         &self,
-        catalog: &Catalog,
+        catalog: &Catalog<U>,
         _slot: u32,
         event: Event,
-        user_data: Option<&Box<dyn Any>>,
+        user_data: &U,
         _status: xerr,
     ) -> Result<()> {
         match event {
             Event::Subscribe(event) => {
                 if let Some(func) = self.subscribe {
-                    if let Some(user_data) = user_data {
-                        func(catalog, &event, Some(user_data.as_ref()))?;
-                    } else {
-                        func(catalog, &event, None)?;
-                    }
+                    func(catalog, &event, user_data)?;
                 }
             }
             Event::Refresh(event) => {
                 if let Some(func) = self.refresh {
-                    if let Some(user_data) = user_data {
-                        func(catalog, &event, Some(user_data.as_ref()))?;
-                    } else {
-                        func(catalog, &event, None)?;
-                    }
+                    func(catalog, &event, user_data)?;
                 }
             }
             Event::Update(event) => {
                 if let Some(func) = self.update {
-                    if let Some(user_data) = user_data {
-                        func(catalog, &event, Some(user_data.as_ref()))?;
-                    } else {
-                        func(catalog, &event, None)?;
-                    }
+                    func(catalog, &event, user_data)?;
                 }
             }
         }
@@ -169,7 +218,25 @@ impl Builder {
     }
 }
 
-type Context = Builder;
+impl<U: Send + 'static> Dispatch for Builder<U> {
+    fn dispatch(
+        &self,
+        handle: xhandle,
+        slot: u32,
+        event_handle: xhandle,
+        event_type: u16,
+        turnkey: u64,
+        status: xerr,
+    ) -> Result<()> {
+        let catalog = Catalog::<U>::from_xhandle(handle)?;
+        let event = Event::from_xhandle_and_type(event_handle, event_type)?;
+
+        // SAFETY: the catalog's only slot turnkey is set to a leaked box of our user data type
+        let user_data = unsafe { (turnkey as *const U).as_ref() }.ok_or(Error::NullObject)?;
+
+        Builder::dispatch(self, &catalog, slot, event, user_data, status)
+    }
+}
 
 #[unsafe(no_mangle)]
 unsafe extern "C" fn _rxegy_catalog_callback(
@@ -183,66 +250,18 @@ unsafe extern "C" fn _rxegy_catalog_callback(
     if let Err(_e) = std::panic::catch_unwind(|| {
         tracing::trace_span!("_rxegy_catalog_callback");
 
-        let catalog = match Catalog::from_xhandle(handle) {
-            Ok(c) => c,
-            Err(e) => {
-                tracing::error!(
-                    "Could not read catalog from handle in catalog callback: {}",
-                    e
-                );
-                return;
-            }
-        };
-
-        let event = match Event::from_xhandle_and_type(event_handle, event_type) {
-            Ok(evt) => evt,
-            Err(e) => {
-                tracing::error!("Could not read keylist catalog event: {}", e);
-                return;
-            }
-        };
-
-        let context_turnkey = match catalog.turnkey() {
-            Ok(ctk) => ctk,
+        // SAFETY: catalogs are only created by the builder, which sets the turnkey
+        let context = match unsafe { container::context(handle) } {
+            Ok(ctx) => ctx,
             Err(e) => {
                 tracing::error!("Could not read catalog turnkey to get context: {}", e);
                 return;
             }
         };
 
-        let context_thin_raw = context_turnkey as *mut Box<dyn Any>;
-        if context_thin_raw.is_null() {
-            tracing::error!("Catalog turnkey was null");
-            return;
-        }
-
-        let context_thin = unsafe { Box::from_raw(context_thin_raw) };
-        let context = match (**context_thin).downcast_ref::<Context>() {
-            Some(ctx) => ctx,
-            None => {
-                tracing::error!("Could not downcast the catalog context from a thin pointer");
-                return;
-            }
-        };
-
-        let user_data = if turnkey == 0 {
-            None
-        } else {
-            let user_data_thin_raw = turnkey as *mut Box<dyn Any>;
-            Some(unsafe { Box::from_raw(user_data_thin_raw) })
-        };
-
-        if let Err(e) = context.dispatch(&catalog, slot, event, user_data.as_deref(), status) {
+        if let Err(e) = context.dispatch(handle, slot, event_handle, event_type, turnkey, status) {
             tracing::error!("The callback returned an error: {}", e);
         }
-
-        // Release the user data pointer
-        if let Some(user_data) = user_data {
-            let _leaked = Box::into_raw(user_data);
-        }
-
-        // Release the context pointer
-        let _leaked = Box::into_raw(context_thin);
     }) {
         tracing::error!("Panic in Keylist Catalog callback, aborting...");
         process::abort()
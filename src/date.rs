@@ -0,0 +1,383 @@
+//! Exegy Date Values
+
+use crate::{
+    Time,
+    error::{Error, Result},
+    format,
+};
+use ref_cast::RefCast;
+use rxegy_sys::{
+    XC_DATE, XC_DATE__bindgen_ty_1, XC_DATETIME, XC_DAYSBACK, XC_FORMAT_CONTROL, xerr,
+};
+use std::{
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+/// The largest year which fits in a date.
+const MAX_YEAR: u32 = (1 << 12) - 1;
+
+/// The largest ordinal which fits in a days-back value.
+const MAX_DAYSBACK_ORDINAL: u32 = (1 << 10) - 1;
+
+/// A calendar date
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, RefCast)]
+#[repr(transparent)]
+pub struct Date(XC_DATE);
+
+impl Date {
+    pub(crate) fn new(inner: XC_DATE) -> Self {
+        Self(inner)
+    }
+
+    /// Create a date from a year, month (1-12) and day of the month (1-31).
+    pub fn from_ymd(year: u32, month: u32, day: u32) -> Result<Self> {
+        if year > MAX_YEAR || day == 0 || day > days_in_month(year, month) {
+            return Err(Error::InvalidDate);
+        }
+
+        Ok(Self(XC_DATE {
+            __bindgen_anon_1: XC_DATE__bindgen_ty_1 {
+                _bitfield_align_1: [],
+                _bitfield_1: XC_DATE__bindgen_ty_1::new_bitfield_1(
+                    day,
+                    month,
+                    year,
+                    rxegy_sys::XC_DATE_ID_PATTERN,
+                ),
+            },
+        }))
+    }
+
+    pub fn year(&self) -> u32 {
+        unsafe { self.0.__bindgen_anon_1 }.xdt_year()
+    }
+
+    pub fn month(&self) -> u32 {
+        unsafe { self.0.__bindgen_anon_1 }.xdt_month()
+    }
+
+    pub fn day(&self) -> u32 {
+        unsafe { self.0.__bindgen_anon_1 }.xdt_day()
+    }
+
+    /// Whether the date has been populated, as unset date fields are zero.
+    pub fn is_set(&self) -> bool {
+        unsafe { self.0.xdt_raw != 0 }
+    }
+}
+
+impl From<&Date> for u32 {
+    fn from(value: &Date) -> Self {
+        unsafe { value.0.xdt_raw }
+    }
+}
+
+impl Date {
+    /// Format the date with XCAPI, returning any error it reports.
+    ///
+    /// This wraps [xcFmtDate](rxegy_sys::xcFmtDate). [`Display`] falls back to the
+    /// [`Debug`](std::fmt::Debug) form instead.
+    pub fn format(&self) -> Result<String> {
+        format::format(self)
+    }
+}
+
+impl format::XcFormat for Date {
+    fn xc_format(&self, ctrl: *mut XC_FORMAT_CONTROL) -> xerr {
+        unsafe { rxegy_sys::xcFmtDate(ctrl, &self.0) }
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        format::display(self, f)
+    }
+}
+
+impl FromStr for Date {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        format::parse_with(s, XC_DATE::default(), |inp, endptr, out| unsafe {
+            rxegy_sys::xcParseDate(inp, endptr, out)
+        })
+        .map(Self)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Date> for chrono::NaiveDate {
+    type Error = Error;
+
+    fn try_from(value: Date) -> Result<Self> {
+        chrono::NaiveDate::from_ymd_opt(value.year() as i32, value.month(), value.day())
+            .ok_or(Error::InvalidDate)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::NaiveDate> for Date {
+    type Error = Error;
+
+    fn try_from(value: chrono::NaiveDate) -> Result<Self> {
+        use chrono::Datelike;
+
+        let year = u32::try_from(value.year()).map_err(|_e| Error::InvalidDate)?;
+        Self::from_ymd(year, value.month(), value.day())
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<Date> for time::Date {
+    type Error = Error;
+
+    fn try_from(value: Date) -> Result<Self> {
+        let month = u8::try_from(value.month())
+            .ok()
+            .and_then(|month| time::Month::try_from(month).ok())
+            .ok_or(Error::InvalidDate)?;
+        time::Date::from_calendar_date(value.year() as i32, month, value.day() as u8)
+            .map_err(|_e| Error::InvalidDate)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<time::Date> for Date {
+    type Error = Error;
+
+    fn try_from(value: time::Date) -> Result<Self> {
+        let year = u32::try_from(value.year()).map_err(|_e| Error::InvalidDate)?;
+        Self::from_ymd(
+            year,
+            u32::from(u8::from(value.month())),
+            u32::from(value.day()),
+        )
+    }
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// A date and a time
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct DateTime(XC_DATETIME);
+
+impl DateTime {
+    /// Create a date-time from its parts.
+    pub fn new(date: Date, time: Time) -> Self {
+        Self(XC_DATETIME {
+            xdtm_time: i32::from(time),
+            xdtm_date: date.0,
+        })
+    }
+
+    /// Retrieve the date.
+    pub fn date(&self) -> Date {
+        Date(self.0.xdtm_date)
+    }
+
+    /// Retrieve the time.
+    pub fn time(&self) -> Time {
+        Time::from(self.0.xdtm_time)
+    }
+}
+
+impl Debug for DateTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("DateTime")
+            .field("date", &self.date())
+            .field("time", &self.time())
+            .finish()
+    }
+}
+
+impl PartialEq for DateTime {
+    fn eq(&self, other: &Self) -> bool {
+        self.date() == other.date() && self.time() == other.time()
+    }
+}
+
+impl Eq for DateTime {}
+
+impl Hash for DateTime {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.date().hash(state);
+        self.time().hash(state);
+    }
+}
+
+impl DateTime {
+    /// Format the date-time with XCAPI, returning any error it reports.
+    ///
+    /// This wraps [xcFmtDateTime](rxegy_sys::xcFmtDateTime). [`Display`] falls back to the
+    /// [`Debug`](std::fmt::Debug) form instead.
+    pub fn format(&self) -> Result<String> {
+        format::format(self)
+    }
+}
+
+impl format::XcFormat for DateTime {
+    fn xc_format(&self, ctrl: *mut XC_FORMAT_CONTROL) -> xerr {
+        unsafe { rxegy_sys::xcFmtDateTime(ctrl, &self.0) }
+    }
+}
+
+impl Display for DateTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        format::display(self, f)
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let empty = XC_DATETIME {
+            xdtm_time: 0,
+            xdtm_date: XC_DATE::default(),
+        };
+        format::parse_with(s, empty, |inp, endptr, out| unsafe {
+            rxegy_sys::xcParseDateTime(inp, endptr, out)
+        })
+        .map(Self)
+    }
+}
+
+/// A span of trading days, counted back from the current day
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, RefCast)]
+#[repr(transparent)]
+pub struct DaysBack(XC_DAYSBACK);
+
+impl DaysBack {
+    /// Create a span of `day_count` days, starting `ordinal` days before the current day.
+    pub fn new(ordinal: u32, day_count: u32) -> Result<Self> {
+        if ordinal > MAX_DAYSBACK_ORDINAL || day_count >= rxegy_sys::XC_MAX_DAYSBACK {
+            return Err(Error::InvalidDate);
+        }
+
+        Ok(Self(XC_DAYSBACK {
+            _bitfield_align_1: [],
+            _bitfield_1: XC_DAYSBACK::new_bitfield_1(
+                ordinal,
+                day_count,
+                rxegy_sys::XC_DAYSBACK_ID_PATTERN,
+            ),
+        }))
+    }
+
+    /// Retrieve how many days before the current day the span starts.
+    pub fn ordinal(&self) -> u32 {
+        self.0.xdb_ordinal()
+    }
+
+    /// Retrieve the number of days in the span.
+    pub fn day_count(&self) -> u32 {
+        self.0.xdb_day_count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ymd_round_trip() {
+        let date = Date::from_ymd(2024, 7, 15).unwrap();
+        assert_eq!((date.year(), date.month(), date.day()), (2024, 7, 15));
+        assert!(date.is_set());
+        assert!(!Date::default().is_set());
+    }
+
+    #[test]
+    fn days_in_each_month() {
+        let days = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+        for (month, days) in (1..=12).zip(days) {
+            assert!(Date::from_ymd(2023, month, days).is_ok(), "{month}/{days}");
+            assert!(
+                matches!(
+                    Date::from_ymd(2023, month, days + 1),
+                    Err(Error::InvalidDate)
+                ),
+                "{month}/{}",
+                days + 1
+            );
+        }
+    }
+
+    #[test]
+    fn leap_years() {
+        for year in [2000, 2024, 1996, 2400] {
+            assert!(Date::from_ymd(year, 2, 29).is_ok(), "{year}");
+        }
+        for year in [1900, 2023, 2100, 2200] {
+            assert!(Date::from_ymd(year, 2, 29).is_err(), "{year}");
+        }
+    }
+
+    #[test]
+    fn invalid_parts() {
+        for (year, month, day) in [(2024, 0, 1), (2024, 13, 1), (2024, 1, 0), (2024, 1, 32)] {
+            assert!(
+                matches!(Date::from_ymd(year, month, day), Err(Error::InvalidDate)),
+                "{year}-{month}-{day}"
+            );
+        }
+    }
+
+    #[test]
+    fn year_bounds() {
+        let date = Date::from_ymd(MAX_YEAR, 12, 31).unwrap();
+        assert_eq!(date.year(), MAX_YEAR);
+        assert!(Date::from_ymd(0, 1, 1).is_ok());
+        assert!(matches!(
+            Date::from_ymd(MAX_YEAR + 1, 1, 1),
+            Err(Error::InvalidDate)
+        ));
+    }
+
+    #[test]
+    fn days_back_bounds() {
+        let span = DaysBack::new(MAX_DAYSBACK_ORDINAL, rxegy_sys::XC_MAX_DAYSBACK - 1).unwrap();
+        assert_eq!(span.ordinal(), MAX_DAYSBACK_ORDINAL);
+        assert_eq!(span.day_count(), rxegy_sys::XC_MAX_DAYSBACK - 1);
+
+        let span = DaysBack::new(0, 0).unwrap();
+        assert_eq!((span.ordinal(), span.day_count()), (0, 0));
+
+        assert!(matches!(
+            DaysBack::new(MAX_DAYSBACK_ORDINAL + 1, 1),
+            Err(Error::InvalidDate)
+        ));
+        assert!(matches!(
+            DaysBack::new(1, rxegy_sys::XC_MAX_DAYSBACK),
+            Err(Error::InvalidDate)
+        ));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_round_trip() {
+        let naive = chrono::NaiveDate::from_ymd_opt(2000, 2, 29).unwrap();
+        let date = Date::try_from(naive).unwrap();
+        assert_eq!(chrono::NaiveDate::try_from(date).unwrap(), naive);
+        assert!(Date::try_from(chrono::NaiveDate::from_ymd_opt(-1, 1, 1).unwrap()).is_err());
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_round_trip() {
+        let civil = time::Date::from_calendar_date(2000, time::Month::February, 29).unwrap();
+        let date = Date::try_from(civil).unwrap();
+        assert_eq!(time::Date::try_from(date).unwrap(), civil);
+    }
+}
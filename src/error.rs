@@ -73,6 +73,30 @@ pub enum Error {
     /// The exponent/price-type value is unknown
     #[error("The exponent/price-type value is unknown")]
     InvalidExponent,
+
+    /// The container slot is neither empty, pending, nor active
+    #[error("The container slot is neither empty, pending, nor active")]
+    SlotStateUnknown,
+
+    /// The container slot has no subscription
+    #[error("The container slot has no subscription")]
+    SlotEmpty,
+
+    /// The container did not accept a request for an item in a batch
+    #[error("The container did not accept the request for this item")]
+    ItemNotRequested,
+
+    /// The time is outside the range which can be represented
+    #[error("The time is outside the range which can be represented")]
+    TimeOutOfRange,
+
+    /// The date is not a valid calendar date, or cannot be represented
+    #[error("The date is not a valid calendar date, or cannot be represented")]
+    InvalidDate,
+
+    /// The string was parsed, but characters were left over afterwards
+    #[error("The string contains trailing characters which could not be parsed")]
+    TrailingInput,
 }
 
 impl From<FromUtf8Error> for Error {
@@ -88,7 +112,7 @@ impl From<FromBytesUntilNulError> for Error {
 }
 
 /// An enumeration of errors which can be encountered in this crate.
-#[derive(Debug, thiserror::Error)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, thiserror::Error)]
 #[repr(u32)]
 #[non_exhaustive]
 pub enum ExegyError {
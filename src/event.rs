@@ -2,8 +2,10 @@
 
 pub use self::{
     equity::{
-        Cancel as EquityCancel, Correction as EquityCorrection, Quote as EquityQuote,
-        Refresh as EquityRefresh, Trade as EquityTrade,
+        Cancel as EquityCancel, CancelData as EquityCancelData, Correction as EquityCorrection,
+        CorrectionData as EquityCorrectionData, Quote as EquityQuote, QuoteData as EquityQuoteData,
+        Refresh as EquityRefresh, RefreshData as EquityRefreshData, Trade as EquityTrade,
+        TradeData as EquityTradeData,
     },
     exchange_statistics::Event as ExchangeStatistics,
     indicative_price::Event as IndicativePrice,
@@ -26,6 +28,7 @@ mod trading_action;
 use crate::{
     error::{ExegyError, Result, Success},
     field::{self, Field as FieldTrait},
+    field_set::{FieldSet, FieldSetLayout},
     key::Key,
     object::Wrapper,
     timing::EventTiming,
@@ -85,6 +88,11 @@ pub trait Common: Wrapper {
         field::get_xc_group_event_timing(self, rxegy_sys::XC_EVENT, Field::TimingGroup)
             .map(EventTiming::new)
     }
+
+    /// Read every field in the given field set from the event in a single call
+    fn read_field_set<T: FieldSetLayout>(&self, set: &FieldSet<T>) -> Result<T> {
+        set.read(self.as_xhandle(), rxegy_sys::XC_EVENT)
+    }
 }
 
 #[derive(Clone, Copy)]
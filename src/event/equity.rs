@@ -1,8 +1,11 @@
 //! Equity events
 
 pub use self::{
-    cancel::Event as Cancel, correction::Event as Correction, quote::Event as Quote,
-    refresh::Event as Refresh, trade::Event as Trade,
+    cancel::{Data as CancelData, Event as Cancel},
+    correction::{Data as CorrectionData, Event as Correction},
+    quote::{Data as QuoteData, Event as Quote},
+    refresh::{Data as RefreshData, Event as Refresh},
+    trade::{Data as TradeData, Event as Trade},
 };
 
 mod cancel;
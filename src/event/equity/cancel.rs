@@ -1,6 +1,11 @@
 //! Equity Cancel Events
 
-use crate::{event::Common, impl_wrapper_on_newtype, object::Kind as ObjectKind};
+use crate::{
+    ExponentKind, FeedId, GroupId, HiTime, InstrumentStatus, MarketStatus, Price, Size, SymbolKind,
+    TickFlags, TradeQualifiers, TradeUpdateFlags, TradedValue, Volume, event::Common,
+    impl_wrapper_on_newtype, object::Kind as ObjectKind,
+};
+use rxegy_sys::XC_GROUP_EQUITY_CANCEL_ALL;
 use std::{ffi::c_void, ptr::NonNull};
 
 /// An equity cancel event
@@ -10,3 +15,119 @@ pub struct Event(NonNull<c_void>);
 impl_wrapper_on_newtype!(Event, ObjectKind::EventEquityCancel);
 
 impl Common for Event {}
+
+crate::impl_event_fields! {
+    Event =>
+
+        // Qualifiers
+        "Get the normalized qualifiers (sale conditions) of the cancelled trade.",
+        Qualifiers, rxegy_sys::XFLD_EVT_EQTY_CANC_QUALS, qualifiers,
+        get_xc_trade_quals, TradeQualifiers, TradeQualifiers::from;
+
+        // Tick Flags
+        "Get the direction of the cancelled trade price relative to the previous trade price.",
+        TickFlags, rxegy_sys::XFLD_EVT_EQTY_CANC_TRADE_TICK_FLAGS, tick_flags,
+        get_xc_tick_flags, TickFlags, TickFlags::from;
+
+        // Update Flags
+        "Get which of the last, open, high, low and close prices the cancelled trade updates.",
+        UpdateFlags, rxegy_sys::XFLD_EVT_EQTY_CANC_TRADE_UPDATE_FLAGS, update_flags,
+        get_xc_equity_trade_update_flags, TradeUpdateFlags, TradeUpdateFlags::from;
+
+        // All Fields
+        "Copy every field of the cancellation into an owned snapshot, which may outlive the callback.
+
+The snapshot is read with a single field group retrieval, so it is consistent with itself.",
+        All, rxegy_sys::XFGRP_EVT_EQTY_CANC_ALL, to_owned,
+        get_xc_group_equity_cancel_all, Data, Data::from;
+}
+
+/// An owned snapshot of an equity trade cancellation event
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct Data {
+    /// The feed the cancellation was published on.
+    pub feed: FeedId,
+    /// The feed group (country) of the instrument.
+    pub group: GroupId,
+    /// The kind of instrument traded.
+    pub symbol_kind: SymbolKind,
+    /// The data source line the cancellation was received on.
+    pub line_id: u16,
+    /// The normalized status of the instrument.
+    pub instrument_status: InstrumentStatus,
+    /// The normalized status of the market.
+    pub market_status: MarketStatus,
+    /// The exponent of every price in the cancellation, if it is a known price type.
+    pub exponent: Option<ExponentKind>,
+    /// Whether the instrument is under a short sale restriction.
+    pub short_sale_restricted: bool,
+    /// Whether the cancelled trade was exempt from trade-through rules.
+    pub trade_through_exempt: bool,
+    /// The exchange timestamp indicating when the cancellation left the exchange.
+    pub exchange_time: HiTime,
+    /// The time the cancellation was received by the appliance.
+    pub receive_time: HiTime,
+    /// The exchange sequence number of the cancellation.
+    pub sequence: u64,
+    /// The normalized qualifiers (sale conditions) of the cancelled trade.
+    pub qualifiers: TradeQualifiers,
+    /// The sub-market the cancelled trade occurred on.
+    pub sub_market: FeedId,
+    /// The price of the cancelled trade.
+    pub price: Price,
+    /// The number of shares in the cancelled trade.
+    pub size: Size,
+    /// The closing price.
+    pub close_price: Price,
+    /// The direction of the cancelled trade price relative to the previous trade price.
+    pub tick_flags: TickFlags,
+    /// The accumulated volume, after the cancellation.
+    pub volume: Volume,
+    /// The accumulated volume which contributes to the VWAP.
+    pub vwap_volume: Volume,
+    /// The accumulated traded value, in the exponent of the prices, if it is a known price type.
+    pub traded_value: Option<TradedValue>,
+    /// The opening price.
+    pub open_price: Price,
+    /// The total change from the previous close.
+    pub total_change: Price,
+    /// The high price.
+    pub high_price: Price,
+    /// The low price.
+    pub low_price: Price,
+}
+
+impl From<XC_GROUP_EQUITY_CANCEL_ALL> for Data {
+    fn from(value: XC_GROUP_EQUITY_CANCEL_ALL) -> Self {
+        let exponent = ExponentKind::try_from(value.xca_price_type()).ok();
+        Self {
+            feed: FeedId::new(value.xca_exchange_code),
+            group: GroupId::new(value.xca_country_code),
+            symbol_kind: SymbolKind::from(value.xca_symbol_type),
+            line_id: value.xca_line_id,
+            instrument_status: InstrumentStatus::new(value.xca_instrument_status),
+            market_status: MarketStatus::new(value.xca_market_status),
+            exponent,
+            short_sale_restricted: value.xca_short_sale_restricted() != 0,
+            trade_through_exempt: value.xca_trade_thru_exempt() != 0,
+            exchange_time: HiTime::from(value.xca_exchange_hitime),
+            receive_time: HiTime::from(value.xca_receive_hitime),
+            sequence: value.xca_sequence,
+            qualifiers: TradeQualifiers::from(value.xca_quals),
+            sub_market: FeedId::new(value.xca_sub_market),
+            price: Price::from(value.xca_price),
+            size: Size::from(value.xca_size),
+            close_price: Price::from(value.xca_close_price),
+            tick_flags: TickFlags::from(value.xca_trade_tick_flags),
+            volume: Volume::from(u64::from(value.xca_volume)),
+            vwap_volume: Volume::from(u64::from(value.xca_vwap_volume)),
+            traded_value: exponent
+                .map(|exponent| TradedValue::new(value.xca_traded_value, exponent)),
+            open_price: Price::from(value.xca_open_price),
+            total_change: Price::from(value.xca_total_change),
+            high_price: Price::from(value.xca_high_price),
+            low_price: Price::from(value.xca_low_price),
+        }
+    }
+}
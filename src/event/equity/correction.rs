@@ -1,6 +1,11 @@
 //! Equity Correction Events
 
-use crate::{event::Common, impl_wrapper_on_newtype, object::Kind as ObjectKind};
+use crate::{
+    ExponentKind, FeedId, GroupId, HiTime, InstrumentStatus, MarketStatus, Price, Size, SymbolKind,
+    TickFlags, TradeQualifiers, TradedValue, Volume, event::Common, impl_wrapper_on_newtype,
+    object::Kind as ObjectKind,
+};
+use rxegy_sys::XC_GROUP_EQUITY_CORRECTION_ALL;
 use std::{ffi::c_void, ptr::NonNull};
 
 /// An equity correction event
@@ -10,3 +15,123 @@ pub struct Event(NonNull<c_void>);
 impl_wrapper_on_newtype!(Event, ObjectKind::EventEquityCorrection);
 
 impl Common for Event {}
+
+crate::impl_event_fields! {
+    Event =>
+
+        // Qualifiers
+        "Get the normalized qualifiers (sale conditions) of the corrected trade.",
+        Qualifiers, rxegy_sys::XFLD_EVT_EQTY_CORR_QUALS, qualifiers,
+        get_xc_trade_quals, TradeQualifiers, TradeQualifiers::from;
+
+        // Tick Flags
+        "Get the direction of the corrected trade price relative to the previous trade price.",
+        TickFlags, rxegy_sys::XFLD_EVT_EQTY_CORR_TRADE_TICK_FLAGS, tick_flags,
+        get_xc_tick_flags, TickFlags, TickFlags::from;
+
+        // All Fields
+        "Copy every field of the correction into an owned snapshot, which may outlive the callback.
+
+The snapshot is read with a single field group retrieval, so it is consistent with itself.",
+        All, rxegy_sys::XFGRP_EVT_EQTY_CORR_ALL, to_owned,
+        get_xc_group_equity_correction_all, Data, Data::from;
+}
+
+/// An owned snapshot of an equity trade correction event
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct Data {
+    /// The feed the correction was published on.
+    pub feed: FeedId,
+    /// The feed group (country) of the instrument.
+    pub group: GroupId,
+    /// The kind of instrument traded.
+    pub symbol_kind: SymbolKind,
+    /// The data source line the correction was received on.
+    pub line_id: u16,
+    /// The normalized status of the instrument.
+    pub instrument_status: InstrumentStatus,
+    /// The normalized status of the market.
+    pub market_status: MarketStatus,
+    /// The exponent of every price in the correction, if it is a known price type.
+    pub exponent: Option<ExponentKind>,
+    /// Whether the instrument is under a short sale restriction.
+    pub short_sale_restricted: bool,
+    /// Whether the corrected trade is exempt from trade-through rules.
+    pub trade_through_exempt: bool,
+    /// The exchange timestamp indicating when the correction left the exchange.
+    pub exchange_time: HiTime,
+    /// The time the correction was received by the appliance.
+    pub receive_time: HiTime,
+    /// The exchange sequence number of the correction.
+    pub sequence: u64,
+    /// The normalized qualifiers (sale conditions) of the corrected trade.
+    pub qualifiers: TradeQualifiers,
+    /// The sub-market the original trade occurred on.
+    pub cancel_sub_market: FeedId,
+    /// The price of the original trade.
+    pub cancel_price: Price,
+    /// The number of shares in the original trade.
+    pub cancel_size: Size,
+    /// The sub-market the corrected trade occurred on.
+    pub correct_sub_market: FeedId,
+    /// The price of the corrected trade.
+    pub correct_price: Price,
+    /// The number of shares in the corrected trade.
+    pub correct_size: Size,
+    /// The closing price.
+    pub close_price: Price,
+    /// The direction of the corrected trade price relative to the previous trade price.
+    pub tick_flags: TickFlags,
+    /// The accumulated volume, after the correction.
+    pub volume: Volume,
+    /// The accumulated volume which contributes to the VWAP.
+    pub vwap_volume: Volume,
+    /// The accumulated traded value, in the exponent of the prices, if it is a known price type.
+    pub traded_value: Option<TradedValue>,
+    /// The opening price.
+    pub open_price: Price,
+    /// The total change from the previous close.
+    pub total_change: Price,
+    /// The high price.
+    pub high_price: Price,
+    /// The low price.
+    pub low_price: Price,
+}
+
+impl From<XC_GROUP_EQUITY_CORRECTION_ALL> for Data {
+    fn from(value: XC_GROUP_EQUITY_CORRECTION_ALL) -> Self {
+        let exponent = ExponentKind::try_from(value.xcc_price_type()).ok();
+        Self {
+            feed: FeedId::new(value.xcc_exchange_code),
+            group: GroupId::new(value.xcc_country_code),
+            symbol_kind: SymbolKind::from(value.xcc_symbol_type),
+            line_id: value.xcc_line_id,
+            instrument_status: InstrumentStatus::new(value.xcc_instrument_status),
+            market_status: MarketStatus::new(value.xcc_market_status),
+            exponent,
+            short_sale_restricted: value.xcc_short_sale_restricted() != 0,
+            trade_through_exempt: value.xcc_trade_thru_exempt() != 0,
+            exchange_time: HiTime::from(value.xcc_exchange_hitime),
+            receive_time: HiTime::from(value.xcc_receive_hitime),
+            sequence: value.xcc_sequence,
+            qualifiers: TradeQualifiers::from(value.xcc_quals),
+            cancel_sub_market: FeedId::new(value.xcc_cancel_sub_market),
+            cancel_price: Price::from(value.xcc_cancel_price),
+            cancel_size: Size::from(value.xcc_cancel_size),
+            correct_sub_market: FeedId::new(value.xcc_correct_sub_market),
+            correct_price: Price::from(value.xcc_correct_price),
+            correct_size: Size::from(value.xcc_correct_size),
+            close_price: Price::from(value.xcc_close_price),
+            tick_flags: TickFlags::from(value.xcc_trade_tick_flags),
+            volume: Volume::from(u64::from(value.xcc_volume)),
+            vwap_volume: Volume::from(u64::from(value.xcc_vwap_volume)),
+            traded_value: exponent
+                .map(|exponent| TradedValue::new(value.xcc_traded_value, exponent)),
+            open_price: Price::from(value.xcc_open_price),
+            total_change: Price::from(value.xcc_total_change),
+            high_price: Price::from(value.xcc_high_price),
+            low_price: Price::from(value.xcc_low_price),
+        }
+    }
+}
@@ -1,6 +1,10 @@
 //! Equity Quote Events
 
-use crate::{event::Common, impl_wrapper_on_newtype, object::Kind as ObjectKind};
+use crate::{
+    ExponentKind, FeedId, GroupId, HiTime, InstrumentStatus, MarketStatus, Price, QuoteQualifiers,
+    Size, SymbolKind, event::Common, impl_wrapper_on_newtype, object::Kind as ObjectKind,
+};
+use rxegy_sys::XC_GROUP_EQUITY_QUOTE_ALL;
 use std::{ffi::c_void, ptr::NonNull};
 
 /// An equity quote event
@@ -10,3 +14,86 @@ pub struct Event(NonNull<c_void>);
 impl_wrapper_on_newtype!(Event, ObjectKind::EventEquityQuote);
 
 impl Common for Event {}
+
+crate::impl_event_fields! {
+    Event =>
+
+        // Qualifiers
+        "Get the normalized qualifiers (quote conditions) of the quote.",
+        Qualifiers, rxegy_sys::XFLD_EVT_EQTY_QUO_QUALS, qualifiers,
+        get_xc_quote_quals, QuoteQualifiers, QuoteQualifiers::from;
+
+        // All Fields
+        "Copy every field of the quote into an owned snapshot, which may outlive the callback.
+
+The snapshot is read with a single field group retrieval, so it is consistent with itself.",
+        All, rxegy_sys::XFGRP_EVT_EQTY_QUO_ALL, to_owned,
+        get_xc_group_equity_quote_all, Data, Data::from;
+}
+
+/// An owned snapshot of an equity quote event
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct Data {
+    /// The feed the quote was published on.
+    pub feed: FeedId,
+    /// The feed group (country) of the instrument.
+    pub group: GroupId,
+    /// The kind of instrument quoted.
+    pub symbol_kind: SymbolKind,
+    /// The data source line the quote was received on.
+    pub line_id: u16,
+    /// The normalized status of the instrument.
+    pub instrument_status: InstrumentStatus,
+    /// The normalized status of the market.
+    pub market_status: MarketStatus,
+    /// The exponent of both prices in the quote, if it is a known price type.
+    pub exponent: Option<ExponentKind>,
+    /// Whether the instrument is under a short sale restriction.
+    pub short_sale_restricted: bool,
+    /// The exchange timestamp indicating when the quote left the exchange.
+    pub exchange_time: HiTime,
+    /// The time the quote was received by the appliance.
+    pub receive_time: HiTime,
+    /// The exchange sequence number of the quote.
+    pub sequence: u64,
+    /// The normalized qualifiers (quote conditions) of the quote.
+    pub qualifiers: QuoteQualifiers,
+    /// The feed which published the bid.
+    pub bid_feed: FeedId,
+    /// The bid price.
+    pub bid_price: Price,
+    /// The number of shares bid.
+    pub bid_size: Size,
+    /// The feed which published the ask.
+    pub ask_feed: FeedId,
+    /// The ask price.
+    pub ask_price: Price,
+    /// The number of shares offered.
+    pub ask_size: Size,
+}
+
+impl From<XC_GROUP_EQUITY_QUOTE_ALL> for Data {
+    fn from(value: XC_GROUP_EQUITY_QUOTE_ALL) -> Self {
+        Self {
+            feed: FeedId::new(value.xcq_exchange_code),
+            group: GroupId::new(value.xcq_country_code),
+            symbol_kind: SymbolKind::from(value.xcq_symbol_type),
+            line_id: value.xcq_line_id,
+            instrument_status: InstrumentStatus::new(value.xcq_instrument_status),
+            market_status: MarketStatus::new(value.xcq_market_status),
+            exponent: ExponentKind::try_from(value.xcq_price_type()).ok(),
+            short_sale_restricted: value.xcq_short_sale_restricted() != 0,
+            exchange_time: HiTime::from(value.xcq_exchange_hitime),
+            receive_time: HiTime::from(value.xcq_receive_hitime),
+            sequence: value.xcq_sequence,
+            qualifiers: QuoteQualifiers::from(value.xcq_quals),
+            bid_feed: FeedId::new(value.xcq_bid_exch),
+            bid_price: Price::from(value.xcq_bid_price),
+            bid_size: Size::from(value.xcq_bid_size),
+            ask_feed: FeedId::new(value.xcq_ask_exch),
+            ask_price: Price::from(value.xcq_ask_price),
+            ask_size: Size::from(value.xcq_ask_size),
+        }
+    }
+}
@@ -1,12 +1,11 @@
 //! Equity Refresh Events
 
-use ref_cast::RefCast;
-use rxegy_sys::XC_REFRESH_QUALS;
-
 use crate::{
-    HiTime, InstrumentStatus, MarketStatus, Price, event::Common, impl_wrapper_on_newtype,
-    object::Kind as ObjectKind,
+    Date, ExponentKind, FeedId, GroupId, HiTime, InstrumentStatus, MarketStatus, Price,
+    QuoteQualifiers, RefreshQualifiers, Result, Size, SymbolKind, TickFlags, TradeQualifiers,
+    TradedValue, Volume, event::Common, field, impl_wrapper_on_newtype, object::Kind as ObjectKind,
 };
+use rxegy_sys::XC_GROUP_EQUITY_REFRESH_ALL_WITH_HITIME;
 use std::{ffi::c_void, ptr::NonNull};
 
 /// An equity refresh event object
@@ -43,6 +42,26 @@ instrument status for each contributing market is provided as an array.",
         MarketStatus, rxegy_sys::XFLD_EVT_EQTY_REFR_MARKET_STATUS, market_status,
         get_xc_trading_state, MarketStatus, MarketStatus::new;
 
+        // Qualifiers
+        "Get the qualifiers of the refresh, indicating why it was published.",
+        Qualifiers, rxegy_sys::XFLD_EVT_EQTY_REFR_QUALS, qualifiers,
+        get_xc_refresh_quals, RefreshQualifiers, RefreshQualifiers::from;
+
+        // Quote Qualifiers
+        "Get the normalized qualifiers (quote conditions) of the current quote.",
+        QuoteQualifiers, rxegy_sys::XFLD_EVT_EQTY_REFR_QUOTE_QUALS, quote_qualifiers,
+        get_xc_quote_quals, QuoteQualifiers, QuoteQualifiers::from;
+
+        // Trade Qualifiers
+        "Get the normalized qualifiers (sale conditions) of the last trade.",
+        TradeQualifiers, rxegy_sys::XFLD_EVT_EQTY_REFR_TRADE_QUALS, trade_qualifiers,
+        get_xc_trade_quals, TradeQualifiers, TradeQualifiers::from;
+
+        // Trading Session Date
+        "Get the date of the market session of the last trade price.",
+        TradingSessionDate, rxegy_sys::XFLD_EVT_EQTY_REFR_TRADING_SESSION_DATE,
+        trading_session_date, get_xc_date, Date, Date::new;
+
         // Closing Price
         "Get the closing price.",
         ClosePrice, rxegy_sys::XFLD_EVT_EQTY_REFR_CLOSE_PRICE, close_price,
@@ -78,15 +97,227 @@ Only populated, cached, and persisted if explicitly provided by the exchange.",
 Only populated, cached, and persisted if explicitly provided by the exchange.",
         AllDayVwap, rxegy_sys::XFLD_EVT_EQTY_REFR_ALLDAY_VWAP, allday_vwap,
         get_i32, Price, Price::from;
+
+        // All Fields
+        "Copy the instrument's image into an owned snapshot, which may outlive the callback.
+
+The snapshot is read with a single field group retrieval, so it is consistent with itself.",
+        All, rxegy_sys::XFGRP_EVT_EQTY_REFR_ALL_WITH_HITIME, to_owned,
+        get_xc_group_equity_refresh_all_with_hitime, Data, Data::from;
 }
 
-/// A set of refresh qualifiers
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, RefCast)]
-#[repr(transparent)]
-pub struct RefreshQuals(XC_REFRESH_QUALS);
+/// The maximum number of contributing markets read from a per-market status array.
+const MAX_CONTRIBUTING_MARKETS: usize = 64;
+
+impl Event {
+    /// Get the normalized trading status/substatus reported by each contributing market, along with
+    /// the feed of that market.
+    ///
+    /// Equity refreshes have no field naming the feed which reported each status, so the feed comes
+    /// from the item key. When that is a single market, it is the only contributor. When it is an
+    /// Exegy-generated composite (see [`FeedId::is_composite()`]), the contributing feeds are not
+    /// published, and are given as `None`.
+    pub fn instrument_status_by_market(&self) -> Result<Vec<(Option<FeedId>, InstrumentStatus)>> {
+        let feed = self.item_key()?.feed_id().contributor();
+        Ok(field::get_array(
+            self,
+            rxegy_sys::XC_EVENT,
+            Field::InstrumentStatus,
+            MAX_CONTRIBUTING_MARKETS,
+        )?
+        .into_iter()
+        .map(|status| (feed, InstrumentStatus::new(status)))
+        .collect())
+    }
+
+    /// Get the normalized trading status/substatus for the market or instrument group reported by
+    /// each contributing market, along with the feed of that market.
+    ///
+    /// Feeds are given as in [`Self::instrument_status_by_market()`].
+    pub fn market_status_by_market(&self) -> Result<Vec<(Option<FeedId>, MarketStatus)>> {
+        let feed = self.item_key()?.feed_id().contributor();
+        Ok(field::get_array(
+            self,
+            rxegy_sys::XC_EVENT,
+            Field::MarketStatus,
+            MAX_CONTRIBUTING_MARKETS,
+        )?
+        .into_iter()
+        .map(|status| (feed, MarketStatus::new(status)))
+        .collect())
+    }
+}
+
+/// An owned snapshot of an equity refresh event
+///
+/// This is the instrument's image when the refresh was published: its current quote, its last
+/// trade, and its session statistics.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct Data {
+    /// The feed the refresh was published on.
+    pub feed: FeedId,
+    /// The feed group (country) of the instrument.
+    pub group: GroupId,
+    /// The kind of instrument refreshed.
+    pub symbol_kind: SymbolKind,
+    /// The data source line the refresh was received on.
+    pub line_id: u16,
+    /// The normalized status of the instrument.
+    pub instrument_status: InstrumentStatus,
+    /// The normalized status of the market.
+    pub market_status: MarketStatus,
+    /// The exponent of every price in the refresh, if it is a known price type.
+    pub exponent: Option<ExponentKind>,
+    /// Whether the instrument is under a short sale restriction.
+    pub short_sale_restricted: bool,
+    /// The exchange timestamp indicating when the refresh left the exchange.
+    pub exchange_time: HiTime,
+    /// The time the refresh was received by the appliance.
+    pub receive_time: HiTime,
+    /// The exchange sequence number of the refresh.
+    pub sequence: u64,
+    /// The qualifiers of the refresh, indicating why it was published.
+    pub qualifiers: RefreshQualifiers,
+    /// The prime "exchange" (i.e., listing exchange feed) for the instrument.
+    pub prime_feed: FeedId,
+    /// The number of shares in a round lot.
+    pub lot_size: u16,
+    /// The feed which published the bid.
+    pub bid_feed: FeedId,
+    /// The bid price.
+    pub bid_price: Price,
+    /// The number of shares bid.
+    pub bid_size: Size,
+    /// The time of the bid.
+    pub bid_time: HiTime,
+    /// The feed which published the ask.
+    pub ask_feed: FeedId,
+    /// The ask price.
+    pub ask_price: Price,
+    /// The number of shares offered.
+    pub ask_size: Size,
+    /// The time of the ask.
+    pub ask_time: HiTime,
+    /// The normalized qualifiers (quote conditions) of the current quote.
+    pub quote_qualifiers: QuoteQualifiers,
+    /// The exchange sequence number of the current quote.
+    pub quote_sequence: u64,
+    /// The feed which published the last trade.
+    pub last_feed: FeedId,
+    /// The last trade price.
+    pub last_price: Price,
+    /// The number of shares in the last trade.
+    pub last_size: Size,
+    /// The time of the last trade.
+    pub last_time: HiTime,
+    /// The direction of the last trade price relative to the previous trade price.
+    pub tick_flags: TickFlags,
+    /// The normalized qualifiers (sale conditions) of the last trade.
+    pub trade_qualifiers: TradeQualifiers,
+    /// The exchange sequence number of the last trade.
+    pub trade_sequence: u64,
+    /// The date of the market session of the last trade price.
+    pub trading_session_date: Date,
+    /// The accumulated volume.
+    pub volume: Volume,
+    /// The accumulated volume which contributes to the VWAP.
+    pub vwap_volume: Volume,
+    /// The accumulated traded value, in the exponent of the prices, if it is a known price type.
+    pub traded_value: Option<TradedValue>,
+    /// The feed which published the opening trade.
+    pub open_feed: FeedId,
+    /// The opening price.
+    pub open_price: Price,
+    /// The time of the opening trade.
+    pub open_time: HiTime,
+    /// The feed which published the high trade.
+    pub high_feed: FeedId,
+    /// The high price.
+    pub high_price: Price,
+    /// The time of the high trade.
+    pub high_time: HiTime,
+    /// The feed which published the low trade.
+    pub low_feed: FeedId,
+    /// The low price.
+    pub low_price: Price,
+    /// The time of the low trade.
+    pub low_time: HiTime,
+    /// The feed which published the closing trade.
+    pub close_feed: FeedId,
+    /// The closing price.
+    pub close_price: Price,
+    /// The number of shares in the closing trade.
+    pub close_size: Size,
+    /// The time of the closing trade.
+    pub close_time: HiTime,
+    /// The previous day's closing price.
+    pub prev_close_price: Price,
+    /// The total change from the previous close.
+    pub total_change: Price,
+    /// The upper limit price band.
+    pub upper_limit_price: Price,
+    /// The lower limit price band.
+    pub lower_limit_price: Price,
+}
 
-impl RefreshQuals {
-    pub(crate) fn new(inner: XC_REFRESH_QUALS) -> Self {
-        Self(inner)
+impl From<XC_GROUP_EQUITY_REFRESH_ALL_WITH_HITIME> for Data {
+    fn from(value: XC_GROUP_EQUITY_REFRESH_ALL_WITH_HITIME) -> Self {
+        let exponent = ExponentKind::try_from(value.xcr_price_type()).ok();
+        Self {
+            feed: FeedId::new(value.xcr_exchange_code),
+            group: GroupId::new(value.xcr_country_code),
+            symbol_kind: SymbolKind::from(value.xcr_symbol_type),
+            line_id: value.xcr_line_id,
+            instrument_status: InstrumentStatus::new(value.xcr_instrument_status),
+            market_status: MarketStatus::new(value.xcr_market_status),
+            exponent,
+            short_sale_restricted: value.xcr_short_sale_restricted() != 0,
+            exchange_time: HiTime::from(value.xcr_exchange_hitime),
+            receive_time: HiTime::from(value.xcr_receive_hitime),
+            sequence: value.xcr_sequence,
+            qualifiers: RefreshQualifiers::from(value.xcr_quals),
+            prime_feed: FeedId::new(value.xcr_prime_exch),
+            lot_size: value.xcr_lot_size,
+            bid_feed: FeedId::new(value.xcr_bid_exch),
+            bid_price: Price::from(value.xcr_bid_price),
+            bid_size: Size::from(value.xcr_bid_size),
+            bid_time: HiTime::from(value.xcr_bid_hitime),
+            ask_feed: FeedId::new(value.xcr_ask_exch),
+            ask_price: Price::from(value.xcr_ask_price),
+            ask_size: Size::from(value.xcr_ask_size),
+            ask_time: HiTime::from(value.xcr_ask_hitime),
+            quote_qualifiers: QuoteQualifiers::from(value.xcr_quote_quals),
+            quote_sequence: value.xcr_quote_sequence,
+            last_feed: FeedId::new(value.xcr_last_exch),
+            last_price: Price::from(value.xcr_last_price),
+            last_size: Size::from(value.xcr_last_size),
+            last_time: HiTime::from(value.xcr_last_hitime),
+            tick_flags: TickFlags::from(value.xcr_trade_tick_flags),
+            trade_qualifiers: TradeQualifiers::from(value.xcr_trade_quals),
+            trade_sequence: value.xcr_trade_sequence,
+            trading_session_date: Date::new(value.xcr_trading_session_date),
+            volume: Volume::from(u64::from(value.xcr_volume)),
+            vwap_volume: Volume::from(u64::from(value.xcr_vwap_volume)),
+            traded_value: exponent
+                .map(|exponent| TradedValue::new(value.xcr_traded_value, exponent)),
+            open_feed: FeedId::new(value.xcr_open_exch),
+            open_price: Price::from(value.xcr_open_price),
+            open_time: HiTime::from(value.xcr_open_hitime),
+            high_feed: FeedId::new(value.xcr_high_exch),
+            high_price: Price::from(value.xcr_high_price),
+            high_time: HiTime::from(value.xcr_high_hitime),
+            low_feed: FeedId::new(value.xcr_low_exch),
+            low_price: Price::from(value.xcr_low_price),
+            low_time: HiTime::from(value.xcr_low_hitime),
+            close_feed: FeedId::new(value.xcr_close_exch),
+            close_price: Price::from(value.xcr_close_price),
+            close_size: Size::from(value.xcr_close_size),
+            close_time: HiTime::from(value.xcr_close_hitime),
+            prev_close_price: Price::from(value.xcr_prev_close),
+            total_change: Price::from(value.xcr_total_change),
+            upper_limit_price: Price::from(value.xcr_upper_limit_price),
+            lower_limit_price: Price::from(value.xcr_lower_limit_price),
+        }
     }
 }
@@ -1,6 +1,11 @@
 //! Equity Trade Events
 
-use crate::{event::Common, impl_wrapper_on_newtype, object::Kind as ObjectKind};
+use crate::{
+    Date, ExponentKind, FeedId, GroupId, HiTime, InstrumentStatus, MarketStatus, OrderRefId,
+    OrderRefIdKind, Price, Result, Size, SymbolKind, TickFlags, TradeQualifiers, TradeUpdateFlags,
+    TradedValue, Volume, event::Common, field, impl_wrapper_on_newtype, object::Kind as ObjectKind,
+};
+use rxegy_sys::XC_GROUP_EQUITY_TRADE_ALL;
 use std::{ffi::c_void, ptr::NonNull};
 
 /// An equity trade event
@@ -10,3 +15,173 @@ pub struct Event(NonNull<c_void>);
 impl_wrapper_on_newtype!(Event, ObjectKind::EventEquityTrade);
 
 impl Common for Event {}
+
+crate::impl_event_fields! {
+    Event =>
+
+        // Qualifiers
+        "Get the normalized qualifiers (sale conditions) of the trade.",
+        Qualifiers, rxegy_sys::XFLD_EVT_EQTY_TRD_QUALS, qualifiers,
+        get_xc_trade_quals, TradeQualifiers, TradeQualifiers::from;
+
+        // Tick Flags
+        "Get the direction of the trade price relative to the previous trade price.",
+        TickFlags, rxegy_sys::XFLD_EVT_EQTY_TRD_TRADE_TICK_FLAGS, tick_flags,
+        get_xc_tick_flags, TickFlags, TickFlags::from;
+
+        // Update Flags
+        "Get which of the last, open, high, low and close prices the trade updates.",
+        UpdateFlags, rxegy_sys::XFLD_EVT_EQTY_TRD_TRADE_UPDATE_FLAGS, update_flags,
+        get_xc_equity_trade_update_flags, TradeUpdateFlags, TradeUpdateFlags::from;
+
+        // Trading Session Date
+        "Get the date of the market session the trade belongs to.",
+        TradingSessionDate, rxegy_sys::XFLD_EVT_EQTY_TRD_TRADING_SESSION_DATE,
+        trading_session_date, get_xc_date, Date, Date::new;
+
+        // All Fields
+        "Copy every field of the trade into an owned snapshot, which may outlive the callback.
+
+The snapshot is read with a single field group retrieval, so it is consistent with itself.",
+        All, rxegy_sys::XFGRP_EVT_EQTY_TRD_ALL, to_owned,
+        get_xc_group_equity_trade_all, Data, Data::from;
+}
+
+impl Event {
+    /// Get the encoding of the order reference IDs carried by the trade.
+    pub fn order_ref_id_kind(&self) -> Result<OrderRefIdKind> {
+        field::get_u8(self, rxegy_sys::XC_EVENT, OrderRefField::Type)
+            .and_then(OrderRefIdKind::try_from)
+    }
+
+    /// Get the reference ID of the resting bid order which participated in the trade.
+    pub fn bid_order_ref(&self) -> Result<OrderRefId> {
+        self.order_ref(OrderRefField::Bid)
+    }
+
+    /// Get the reference ID of the resting ask order which participated in the trade.
+    pub fn ask_order_ref(&self) -> Result<OrderRefId> {
+        self.order_ref(OrderRefField::Ask)
+    }
+
+    /// Get the exchange-assigned reference ID of the trade itself.
+    pub fn trade_ref(&self) -> Result<OrderRefId> {
+        self.order_ref(OrderRefField::Trade)
+    }
+
+    fn order_ref(&self, order_ref: OrderRefField) -> Result<OrderRefId> {
+        let kind = self.order_ref_id_kind()?;
+        field::get_xc_order_ref_id(self, rxegy_sys::XC_EVENT, order_ref)
+            .map(|inner| OrderRefId::new(inner, kind))
+    }
+}
+
+/// An enumeration of order reference fields, which must be read along with their encoding
+#[derive(Clone, Copy, Debug)]
+#[repr(u64)]
+enum OrderRefField {
+    /// The encoding of every order reference ID in the trade
+    Type = rxegy_sys::XFLD_EVT_EQTY_TRD_ORDER_REF_ID_TYPE,
+    /// The resting bid order
+    Bid = rxegy_sys::XFLD_EVT_EQTY_TRD_BID_ORDER_REF,
+    /// The resting ask order
+    Ask = rxegy_sys::XFLD_EVT_EQTY_TRD_ASK_ORDER_REF,
+    /// The trade itself
+    Trade = rxegy_sys::XFLD_EVT_EQTY_TRD_TRADE_REF,
+}
+
+impl field::Field for OrderRefField {
+    fn to_u64(&self) -> u64 {
+        *self as u64
+    }
+}
+
+/// An owned snapshot of an equity trade event
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct Data {
+    /// The feed the trade was published on.
+    pub feed: FeedId,
+    /// The feed group (country) of the instrument.
+    pub group: GroupId,
+    /// The kind of instrument traded.
+    pub symbol_kind: SymbolKind,
+    /// The data source line the trade was received on.
+    pub line_id: u16,
+    /// The normalized status of the instrument.
+    pub instrument_status: InstrumentStatus,
+    /// The normalized status of the market.
+    pub market_status: MarketStatus,
+    /// The exponent of every price in the trade, if it is a known price type.
+    pub exponent: Option<ExponentKind>,
+    /// Whether the instrument is under a short sale restriction.
+    pub short_sale_restricted: bool,
+    /// Whether the trade is exempt from trade-through rules.
+    pub trade_through_exempt: bool,
+    /// The exchange timestamp indicating when the trade left the exchange.
+    pub exchange_time: HiTime,
+    /// The time the trade was received by the appliance.
+    pub receive_time: HiTime,
+    /// The exchange sequence number of the trade.
+    pub sequence: u64,
+    /// The normalized qualifiers (sale conditions) of the trade.
+    pub qualifiers: TradeQualifiers,
+    /// The sub-market the trade occurred on.
+    pub sub_market: FeedId,
+    /// The trade price.
+    pub price: Price,
+    /// The number of shares traded.
+    pub size: Size,
+    /// The closing price.
+    pub close_price: Price,
+    /// The direction of the trade price relative to the previous trade price.
+    pub tick_flags: TickFlags,
+    /// The accumulated volume.
+    pub volume: Volume,
+    /// The accumulated volume which contributes to the VWAP.
+    pub vwap_volume: Volume,
+    /// The accumulated traded value, in the exponent of the prices, if it is a known price type.
+    pub traded_value: Option<TradedValue>,
+    /// The opening price.
+    pub open_price: Price,
+    /// The total change from the previous close.
+    pub total_change: Price,
+    /// The high price.
+    pub high_price: Price,
+    /// The low price.
+    pub low_price: Price,
+}
+
+impl From<XC_GROUP_EQUITY_TRADE_ALL> for Data {
+    fn from(value: XC_GROUP_EQUITY_TRADE_ALL) -> Self {
+        let exponent = ExponentKind::try_from(value.xct_price_type()).ok();
+        Self {
+            feed: FeedId::new(value.xct_exchange_code),
+            group: GroupId::new(value.xct_country_code),
+            symbol_kind: SymbolKind::from(value.xct_symbol_type),
+            line_id: value.xct_line_id,
+            instrument_status: InstrumentStatus::new(value.xct_instrument_status),
+            market_status: MarketStatus::new(value.xct_market_status),
+            exponent,
+            short_sale_restricted: value.xct_short_sale_restricted() != 0,
+            trade_through_exempt: value.xct_trade_thru_exempt() != 0,
+            exchange_time: HiTime::from(value.xct_exchange_hitime),
+            receive_time: HiTime::from(value.xct_receive_hitime),
+            sequence: value.xct_sequence,
+            qualifiers: TradeQualifiers::from(value.xct_quals),
+            sub_market: FeedId::new(value.xct_sub_market),
+            price: Price::from(value.xct_price),
+            size: Size::from(value.xct_size),
+            close_price: Price::from(value.xct_close_price),
+            tick_flags: TickFlags::from(value.xct_trade_tick_flags),
+            volume: Volume::from(u64::from(value.xct_volume)),
+            vwap_volume: Volume::from(u64::from(value.xct_vwap_volume)),
+            traded_value: exponent
+                .map(|exponent| TradedValue::new(value.xct_traded_value, exponent)),
+            open_price: Price::from(value.xct_open_price),
+            total_change: Price::from(value.xct_total_change),
+            high_price: Price::from(value.xct_high_price),
+            low_price: Price::from(value.xct_low_price),
+        }
+    }
+}
@@ -8,6 +8,7 @@ mod xx;
 use crate::group::Group;
 use ref_cast::RefCast;
 use rxegy_sys::XC_EXCHANGE_ID;
+use std::ffi::c_char;
 
 /// Exegy Feed ID
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, RefCast)]
@@ -19,6 +20,32 @@ impl Id {
     pub(crate) fn new(inner: XC_EXCHANGE_ID) -> Self {
         Self(inner)
     }
+
+    #[inline(always)]
+    pub(crate) fn inner(&self) -> XC_EXCHANGE_ID {
+        self.0
+    }
+
+    /// Whether this is an Exegy-generated view built from several markets, such as the NBBO, a
+    /// quote montage, or a user-defined composite.
+    ///
+    /// Exegy reserves exchange codes beginning with a backslash for these views.
+    pub fn is_composite(&self) -> bool {
+        self.0.xex_ch[0] as u8 == b'\\'
+    }
+
+    /// The feed which contributes every value for this feed, unless it is a composite.
+    pub(crate) fn contributor(&self) -> Option<Self> {
+        (!self.is_composite()).then_some(*self)
+    }
+}
+
+impl From<[u8; 2]> for Id {
+    fn from(value: [u8; 2]) -> Self {
+        Self(XC_EXCHANGE_ID {
+            xex_ch: value.map(|ch| ch as c_char),
+        })
+    }
 }
 
 /// A trait for group-specific feeds to retrieve
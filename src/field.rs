@@ -2,13 +2,20 @@
 
 use crate::{
     error::{Result, Success},
+    field_set::FieldValue,
     object::Wrapper,
 };
 use rxegy_sys::{
-    XC_ALTERNATE_ID, XC_COUNTRY_ID, XC_CURRENCY_ID, XC_EXCHANGE_ID, XC_GROUP_EVENT_TIMING, XC_KEY,
-    XC_REFRESH_QUALS, XC_TRADE_VENUE, XC_TRADING_STATE,
+    XC_ALTERNATE_ID, XC_COUNTRY_ID, XC_CURRENCY_ID, XC_DATE, XC_EQUITY_TRADE_UPDATE_FLAGS,
+    XC_EXCHANGE_ID, XC_GROUP_EQUITY_CANCEL_ALL, XC_GROUP_EQUITY_CORRECTION_ALL,
+    XC_GROUP_EQUITY_QUOTE_ALL, XC_GROUP_EQUITY_REFRESH_ALL_WITH_HITIME, XC_GROUP_EQUITY_TRADE_ALL,
+    XC_GROUP_EVENT_TIMING, XC_KEY, XC_ORDER_REF_ID, XC_QUOTE_QUALS, XC_REFRESH_QUALS,
+    XC_TICK_FLAGS, XC_TRADE_QUALS, XC_TRADE_VENUE, XC_TRADING_STATE,
+};
+use std::{
+    ffi::{CStr, CString},
+    mem,
 };
-use std::ffi::{CStr, CString};
 
 /// A marker trait for field types
 pub trait Field {
@@ -42,6 +49,33 @@ macro_rules! impl_getter {
     };
 }
 
+macro_rules! impl_group_getter {
+    ($value:ty, $funcname:ident) => {
+        /// Retrieve the contents of the given field group of type `$value`
+        pub(crate) fn $funcname<O: $crate::object::Wrapper, F: $crate::field::Field>(
+            object: &O,
+            slot: u32,
+            field: F,
+        ) -> $crate::error::Result<$value> {
+            // Groups containing qualifier unions have no Default impl, but are plain C data
+            let mut obuf = unsafe { ::std::mem::zeroed::<$value>() };
+            let status = unsafe {
+                rxegy_sys::xcGetFieldGroup(
+                    object.as_xhandle(),
+                    slot,
+                    field.to_u64(),
+                    &mut obuf as *mut $value as *mut _,
+                    ::std::mem::size_of::<$value>() as u32,
+                )
+            };
+
+            $crate::error::Success::try_from(status)?;
+
+            Ok(obuf)
+        }
+    };
+}
+
 macro_rules! impl_setter {
     ($value:ty, $funcname:ident, $xcfunc:ident) => {
         /// Retrieve the contents of the given field of type `$value`
@@ -133,6 +167,83 @@ pub fn get_fixedstring<O: Wrapper, F: Field>(
     Ok(String::from_utf8(obuf)?)
 }
 
+/// Retrieve up to `max` consecutive elements of an array field
+pub(crate) fn get_array<O: Wrapper, F: Field, T: FieldValue>(
+    object: &O,
+    slot: u32,
+    field: F,
+    max: usize,
+) -> Result<Vec<T>> {
+    let mut obuf = vec![unsafe { mem::zeroed::<T>() }; max];
+    let mut count = max as u32;
+    let status = unsafe {
+        rxegy_sys::xcGetFieldArray(
+            object.as_xhandle(),
+            slot,
+            field.to_u64(),
+            obuf.as_mut_ptr() as *mut _,
+            mem::size_of::<T>() as u32,
+            &mut count,
+        )
+    };
+
+    Success::try_from(status)?;
+
+    obuf.truncate(count as usize);
+    Ok(obuf)
+}
+
+/// Overwrite consecutive elements of an array field, returning the number of elements written
+#[allow(dead_code)]
+pub(crate) fn set_array<O: Wrapper, F: Field, T: FieldValue>(
+    object: &O,
+    slot: u32,
+    field: F,
+    ibuf: &[T],
+) -> Result<u32> {
+    let mut count = ibuf.len() as u32;
+    let status = unsafe {
+        rxegy_sys::xcSetFieldArray(
+            object.as_xhandle(),
+            slot,
+            field.to_u64(),
+            ibuf.as_ptr() as *const _,
+            mem::size_of::<T>() as u32,
+            &mut count,
+        )
+    };
+
+    Success::try_from(status)?;
+
+    Ok(count)
+}
+
+/// Retrieve up to `max` consecutive elements of a field group array
+pub(crate) fn get_group_array<O: Wrapper, T: FieldValue>(
+    object: &O,
+    slot: u32,
+    group: u64,
+    max: usize,
+) -> Result<Vec<T>> {
+    let mut obuf = vec![unsafe { mem::zeroed::<T>() }; max];
+    let mut count = max as u32;
+    let status = unsafe {
+        rxegy_sys::xcGetFieldGroupArray(
+            object.as_xhandle(),
+            slot,
+            group,
+            obuf.as_mut_ptr() as *mut _,
+            mem::size_of::<T>() as u32,
+            &mut count,
+        )
+    };
+
+    Success::try_from(status)?;
+
+    obuf.truncate(count as usize);
+    Ok(obuf)
+}
+
 impl_getter!(u8, get_u8, xcGetField);
 impl_setter!(u8, set_u8, xcSetField);
 
@@ -149,13 +260,34 @@ impl_getter!(i32, get_i32, xcGetField);
 impl_getter!(XC_ALTERNATE_ID, get_xc_alternate_id, xcGetField);
 impl_getter!(XC_COUNTRY_ID, get_xc_country_id, xcGetField);
 impl_getter!(XC_CURRENCY_ID, get_xc_currency_id, xcGetField);
+impl_getter!(XC_DATE, get_xc_date, xcGetField);
+impl_getter!(
+    XC_EQUITY_TRADE_UPDATE_FLAGS,
+    get_xc_equity_trade_update_flags,
+    xcGetField
+);
 impl_getter!(XC_EXCHANGE_ID, get_xc_exchange_id, xcGetField);
 impl_getter!(
     XC_GROUP_EVENT_TIMING,
     get_xc_group_event_timing,
     xcGetFieldGroup
 );
+impl_group_getter!(XC_GROUP_EQUITY_CANCEL_ALL, get_xc_group_equity_cancel_all);
+impl_group_getter!(
+    XC_GROUP_EQUITY_CORRECTION_ALL,
+    get_xc_group_equity_correction_all
+);
+impl_group_getter!(XC_GROUP_EQUITY_QUOTE_ALL, get_xc_group_equity_quote_all);
+impl_group_getter!(
+    XC_GROUP_EQUITY_REFRESH_ALL_WITH_HITIME,
+    get_xc_group_equity_refresh_all_with_hitime
+);
+impl_group_getter!(XC_GROUP_EQUITY_TRADE_ALL, get_xc_group_equity_trade_all);
 impl_getter!(XC_KEY, get_xc_key, xcGetField);
+impl_getter!(XC_ORDER_REF_ID, get_xc_order_ref_id, xcGetField);
+impl_getter!(XC_QUOTE_QUALS, get_xc_quote_quals, xcGetField);
 impl_getter!(XC_REFRESH_QUALS, get_xc_refresh_quals, xcGetField);
+impl_getter!(XC_TICK_FLAGS, get_xc_tick_flags, xcGetField);
+impl_getter!(XC_TRADE_QUALS, get_xc_trade_quals, xcGetField);
 impl_getter!(XC_TRADE_VENUE, get_xc_trade_venue, xcGetField);
 impl_getter!(XC_TRADING_STATE, get_xc_trading_state, xcGetField);
@@ -0,0 +1,337 @@
+//! Bulk field reads via field sets
+
+use crate::error::{ExegyError, Result, Success};
+use rxegy_sys::{
+    XC_COUNTRY_ID, XC_EXCHANGE_ID, XC_FIELD_DESC, XC_GROUP_EQUITY_CANCEL_ALL,
+    XC_GROUP_EQUITY_CORRECTION_ALL, XC_GROUP_EQUITY_QUOTE_ALL, XC_GROUP_EQUITY_TRADE_ALL,
+    XC_GROUP_EVENT_TIMING, XC_KEY, XC_TRADING_STATE, xhandle,
+};
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    marker::PhantomData,
+    mem,
+    ptr::NonNull,
+};
+
+/// A plain-data type which XCAPI may write a field or field group value into.
+///
+/// Arrays are read as field arrays (or field group arrays), one element per array entry.
+///
+/// # Safety
+///
+/// Every bit pattern, including all zeroes, must be a valid value of the implementing type.
+pub unsafe trait FieldValue: Copy + Send + 'static {
+    /// The size of each element written by XCAPI.
+    const ELEMENT_SIZE: usize = mem::size_of::<Self>();
+    /// The number of elements written by XCAPI.
+    const COUNT: usize = 1;
+}
+
+macro_rules! impl_field_value {
+    ($($value:ty),* $(,)?) => {
+        $(
+            unsafe impl FieldValue for $value {}
+        )*
+    };
+}
+
+impl_field_value!(
+    u8,
+    u16,
+    u32,
+    u64,
+    i8,
+    i16,
+    i32,
+    i64,
+    f32,
+    f64,
+    XC_COUNTRY_ID,
+    XC_EXCHANGE_ID,
+    XC_GROUP_EQUITY_CANCEL_ALL,
+    XC_GROUP_EQUITY_CORRECTION_ALL,
+    XC_GROUP_EQUITY_QUOTE_ALL,
+    XC_GROUP_EQUITY_TRADE_ALL,
+    XC_GROUP_EVENT_TIMING,
+    XC_KEY,
+    XC_TRADING_STATE,
+);
+
+unsafe impl<T: FieldValue, const N: usize> FieldValue for [T; N] {
+    const ELEMENT_SIZE: usize = mem::size_of::<T>();
+    const COUNT: usize = N;
+}
+
+/// A single field or field group within a [`FieldSetLayout`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FieldSetMember {
+    /// The XCAPI field or field group ID.
+    pub id: u64,
+    /// The offset of the member's value within the layout, in bytes.
+    pub offset: usize,
+    /// The size of each element of the member's value.
+    pub element_size: usize,
+    /// The number of elements in the member's value.
+    pub count: usize,
+}
+
+impl FieldSetMember {
+    /// Describe a member of type `V` at the given offset.
+    pub const fn new<V: FieldValue>(id: u64, offset: usize) -> Self {
+        Self {
+            id,
+            offset,
+            element_size: V::ELEMENT_SIZE,
+            count: V::COUNT,
+        }
+    }
+}
+
+/// A struct which may be populated by reading a [`FieldSet`].
+///
+/// This is normally implemented with the [`field_set!`](crate::field_set) macro.
+///
+/// # Safety
+///
+/// Every member must lie entirely within the implementing type, must not overlap any other member,
+/// and must be valid for every bit pattern XCAPI may write, including all zeroes.
+pub unsafe trait FieldSetLayout: Copy + Send + 'static {
+    /// The fields and field groups which make up the layout.
+    const MEMBERS: &'static [FieldSetMember];
+}
+
+/// Declare a struct of fields and field groups which may be read in a single call.
+///
+/// Each member names the XCAPI field or field group ID it is read from, and must have a type which
+/// implements [`FieldValue`].
+///
+/// ```ignore
+/// rxegy::field_set! {
+///     /// The closing fields of a refresh.
+///     pub struct Close {
+///         pub price: i32 = rxegy_sys::XFLD_EVT_EQTY_REFR_CLOSE_PRICE,
+///         pub hitime: u64 = rxegy_sys::XFLD_EVT_EQTY_REFR_CLOSE_HITIME,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! field_set {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$fmeta:meta])* $fvis:vis $field:ident: $value:ty = $id:expr),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy)]
+        #[repr(C)]
+        $vis struct $name {
+            $(
+                $(#[$fmeta])*
+                $fvis $field: $value,
+            )*
+        }
+
+        unsafe impl $crate::FieldSetLayout for $name {
+            const MEMBERS: &'static [$crate::FieldSetMember] = &[
+                $(
+                    $crate::FieldSetMember::new::<$value>($id, ::std::mem::offset_of!($name, $field)),
+                )*
+            ];
+        }
+    };
+}
+
+/// A reusable description of the fields to read into a `T`.
+pub struct FieldSet<T> {
+    descs: Vec<XC_FIELD_DESC>,
+    _layout: PhantomData<fn() -> T>,
+}
+
+impl<T> Debug for FieldSet<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("FieldSet")
+            .field("members", &self.descs.len())
+            .finish()
+    }
+}
+
+impl<T: FieldSetLayout> Default for FieldSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FieldSetLayout> Clone for FieldSet<T> {
+    fn clone(&self) -> Self {
+        Self {
+            descs: self.descs.clone(),
+            _layout: PhantomData,
+        }
+    }
+}
+
+impl<T: FieldSetLayout> FieldSet<T> {
+    /// Create the field set for the layout of `T`.
+    pub fn new() -> Self {
+        let descs = T::MEMBERS
+            .iter()
+            .map(|member| {
+                // The conversion union is all zeroes when no conversion is requested
+                let mut desc = unsafe { mem::zeroed::<XC_FIELD_DESC>() };
+                desc.xfd_id = member.id;
+                desc.xfd_elmsiz = member.element_size as u32;
+                desc.xfd_in_count = member.count as u32;
+                desc.xfd_desc_size = mem::size_of::<XC_FIELD_DESC>() as u8;
+                desc
+            })
+            .collect();
+
+        Self {
+            descs,
+            _layout: PhantomData,
+        }
+    }
+
+    /// Copy the descriptors, pointing each at its member within the given value.
+    fn descs_for(&self, value: *mut T) -> Vec<XC_FIELD_DESC> {
+        T::MEMBERS
+            .iter()
+            .zip(self.descs.iter())
+            .map(|(member, desc)| {
+                let mut desc = *desc;
+                desc.xfd_buffer_address =
+                    unsafe { value.cast::<u8>().add(member.offset) } as usize as u64;
+                desc
+            })
+            .collect()
+    }
+
+    /// Read every member of the layout from the given slot in a single call.
+    ///
+    /// Members which could not be read are left zeroed.
+    pub(crate) fn read(&self, handle: xhandle, slot: u32) -> Result<T> {
+        let mut value = unsafe { mem::zeroed::<T>() };
+        let mut descs = self.descs_for(&mut value);
+
+        Success::try_from(unsafe {
+            rxegy_sys::xcGetFieldSet(handle, slot, descs.as_mut_ptr(), descs.len() as u32)
+        })?;
+
+        Ok(value)
+    }
+
+    /// Attach the field set to a container slot, so XCAPI populates it before each callback.
+    pub(crate) fn attach(&self, handle: xhandle, slot: u32) -> Result<AttachedFieldSet<T>> {
+        let attachment = Box::into_raw(Box::new(Attachment {
+            descs: Vec::new(),
+            value: unsafe { mem::zeroed::<T>() },
+        }));
+
+        unsafe {
+            (*attachment).descs = self.descs_for(&raw mut (*attachment).value);
+        }
+
+        let status = unsafe {
+            rxegy_sys::xcAttachFieldSet(
+                handle,
+                slot,
+                (*attachment).descs.as_mut_ptr(),
+                (*attachment).descs.len() as u32,
+            )
+        };
+
+        if let Err(e) = Success::try_from(status) {
+            let _attachment = unsafe { Box::from_raw(attachment) };
+            return Err(e.into());
+        }
+
+        Ok(AttachedFieldSet {
+            handle,
+            slot,
+            attachment: unsafe { NonNull::new_unchecked(attachment) },
+        })
+    }
+}
+
+/// The descriptors and output buffer XCAPI writes to for an attached field set.
+struct Attachment<T> {
+    descs: Vec<XC_FIELD_DESC>,
+    value: T,
+}
+
+/// A field set attached to a container slot, which XCAPI populates before each callback on it.
+///
+/// Dropping the attachment detaches the field set, and so it may not be dropped from within a
+/// callback. Clearing the slot also detaches the field set.
+pub struct AttachedFieldSet<T> {
+    handle: xhandle,
+    slot: u32,
+    attachment: NonNull<Attachment<T>>,
+}
+
+// The buffers are only written by XCAPI before a callback on the slot, see `get()`
+unsafe impl<T: Send> Send for AttachedFieldSet<T> {}
+
+impl<T> Debug for AttachedFieldSet<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("AttachedFieldSet")
+            .field("handle", &self.handle)
+            .field("slot", &self.slot)
+            .finish()
+    }
+}
+
+impl<T: FieldSetLayout> AttachedFieldSet<T> {
+    /// The slot the field set is attached to.
+    pub fn slot(&self) -> u32 {
+        self.slot
+    }
+
+    /// Copy the values XCAPI populated before the current callback.
+    ///
+    /// # Safety
+    ///
+    /// XCAPI writes to the field set on the callback thread before each callback on the slot, so
+    /// this may only be called from within a callback on the container.
+    pub unsafe fn get(&self) -> T {
+        unsafe { (&raw const (*self.attachment.as_ptr()).value).read() }
+    }
+
+    /// Detach the field set from its slot.
+    ///
+    /// This may not be called from within a callback.
+    pub fn detach(mut self) -> Result<()> {
+        let result = self.release();
+        // The buffers have either been freed, or are still in use by XCAPI and must leak
+        mem::forget(self);
+        result
+    }
+}
+
+impl<T> AttachedFieldSet<T> {
+    /// Detach the field set, and free its buffers if XCAPI no longer uses them.
+    fn release(&mut self) -> Result<()> {
+        let status = unsafe { rxegy_sys::xcDetachFieldSet(self.handle, self.slot) };
+        match Success::try_from(status) {
+            // The slot was cleared or the container destroyed, so XCAPI no longer uses the buffers
+            Ok(_) | Err(ExegyError::NoEntry) | Err(ExegyError::BadHandle) => {
+                let _attachment = unsafe { Box::from_raw(self.attachment.as_ptr()) };
+                Ok(())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl<T> Drop for AttachedFieldSet<T> {
+    fn drop(&mut self) {
+        if let Err(e) = self.release() {
+            tracing::error!(
+                slot = self.slot,
+                "Could not detach field set, leaking its buffers: {}",
+                e
+            );
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Rust helpers for the xcFmt and xcParse families of functions
+
+use crate::error::{Error, Result, Success};
+use rxegy_sys::{XC_FORMAT_CONTROL, xerr};
+use std::{
+    ffi::{CStr, CString, c_char},
+    fmt::{Debug, Formatter, Result as FmtResult},
+    ptr,
+};
+
+/// The size of the buffer XCAPI formats into, which fits every formatted type.
+const FORMAT_BUFFER_SIZE: usize = 128;
+
+/// A value which XCAPI can format, and which falls back to its `Debug` form when it cannot.
+pub(crate) trait XcFormat: Debug {
+    /// Format the value into the given control block with the matching xcFmt function.
+    fn xc_format(&self, ctrl: *mut XC_FORMAT_CONTROL) -> xerr;
+}
+
+/// Format a value with XCAPI into the given buffer.
+fn format_into<'a, T: XcFormat + ?Sized>(
+    value: &T,
+    outbuf: &'a mut [c_char; FORMAT_BUFFER_SIZE],
+) -> Result<&'a str> {
+    let mut ctrl = XC_FORMAT_CONTROL::default();
+    Success::try_from(unsafe {
+        rxegy_sys::xcFmtInitialize(&mut ctrl, outbuf.as_mut_ptr(), outbuf.len() as u32)
+    })?;
+    Success::try_from(value.xc_format(&mut ctrl))?;
+
+    Ok(unsafe { CStr::from_ptr(outbuf.as_ptr()) }.to_str()?)
+}
+
+/// Format a value with XCAPI, returning any error it reports.
+pub(crate) fn format<T: XcFormat + ?Sized>(value: &T) -> Result<String> {
+    let mut outbuf = [0 as c_char; FORMAT_BUFFER_SIZE];
+    format_into(value, &mut outbuf).map(str::to_owned)
+}
+
+/// Write a value as formatted by XCAPI, or in its `Debug` form if XCAPI cannot format it.
+///
+/// A `Display` implementation may only fail when the formatter does, or `to_string()` and
+/// `format!` would panic on any value XCAPI rejects.
+pub(crate) fn display<T: XcFormat + ?Sized>(value: &T, f: &mut Formatter<'_>) -> FmtResult {
+    let mut outbuf = [0 as c_char; FORMAT_BUFFER_SIZE];
+    match format_into(value, &mut outbuf) {
+        Ok(formatted) => f.write_str(formatted),
+        Err(_e) => Debug::fmt(value, f),
+    }
+}
+
+/// Parse a string with the given XCAPI parsing function, starting from an empty value.
+///
+/// The whole string must be consumed by the parser.
+pub(crate) fn parse_with<T>(
+    s: &str,
+    mut value: T,
+    parse: impl FnOnce(*const c_char, *mut *mut c_char, *mut T) -> xerr,
+) -> Result<T> {
+    let input = CString::new(s)?;
+    let mut end = ptr::null_mut();
+
+    Success::try_from(parse(input.as_ptr(), &mut end, &mut value))?;
+
+    if !end.is_null() && end.cast_const() != unsafe { input.as_ptr().add(s.len()) } {
+        return Err(Error::TrailingInput);
+    }
+
+    Ok(value)
+}
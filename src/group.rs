@@ -2,9 +2,10 @@
 
 use ref_cast::RefCast;
 use rxegy_sys::XC_COUNTRY_ID;
+use std::ffi::c_char;
 
 /// Exegy "country" code -- actually a feed ID group
-#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd, RefCast)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, RefCast)]
 #[repr(transparent)]
 pub struct Id(XC_COUNTRY_ID);
 
@@ -13,6 +14,19 @@ impl Id {
     pub(crate) fn new(inner: XC_COUNTRY_ID) -> Self {
         Self(inner)
     }
+
+    #[inline(always)]
+    pub(crate) fn inner(&self) -> XC_COUNTRY_ID {
+        self.0
+    }
+}
+
+impl From<[u8; 2]> for Id {
+    fn from(value: [u8; 2]) -> Self {
+        Self(XC_COUNTRY_ID {
+            xcc_ch: value.map(|ch| ch as c_char),
+        })
+    }
 }
 
 /// A group of feeds
@@ -0,0 +1,320 @@
+//! Exegy Time Values
+
+use crate::{
+    error::{Error, Result, Success},
+    format,
+};
+use rxegy_sys::{XC_FORMAT_CONTROL, xerr};
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    ops::Sub,
+    time::{Duration, SystemTime},
+};
+
+/// An Exegy hi-resolution timestamp, in nanoseconds since the Unix epoch
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct HiTime(u64);
+
+impl From<u64> for HiTime {
+    #[inline(always)]
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<HiTime> for u64 {
+    #[inline(always)]
+    fn from(value: HiTime) -> Self {
+        value.0
+    }
+}
+
+impl HiTime {
+    /// Retrieve the current time from XCAPI's clock.
+    pub fn now() -> Self {
+        Self(unsafe { rxegy_sys::xcGetCurrentHiTime() })
+    }
+
+    /// Convert the timestamp to a system time.
+    pub fn to_system_time(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0)
+    }
+
+    /// Convert the timestamp to a whole-second XCAPI time.
+    pub fn to_time(&self) -> Result<Time> {
+        let mut out = 0;
+        Success::try_from(unsafe { rxegy_sys::xcCvtHiTimeToTime(&self.0, &mut out) })?;
+        Ok(Time(out))
+    }
+
+    /// Convert the timestamp to an exchange-local XCAPI time, given the exchange's offset from UTC.
+    pub fn to_extime(&self, timezone_offset: i32) -> Result<ExTime> {
+        let mut out = 0;
+        Success::try_from(unsafe {
+            rxegy_sys::xcCvtHiTimeToExTime(&self.0, timezone_offset, &mut out)
+        })?;
+        Ok(ExTime(out))
+    }
+
+    /// The signed difference between this timestamp and an earlier one.
+    pub fn delta_since(&self, earlier: HiTime) -> HiTimeDelta {
+        HiTimeDelta(self.0.wrapping_sub(earlier.0) as i64)
+    }
+}
+
+impl HiTime {
+    /// Format the timestamp with XCAPI, returning any error it reports.
+    ///
+    /// This wraps [xcFmtHiTime](rxegy_sys::xcFmtHiTime). [`Display`] falls back to the
+    /// [`Debug`](std::fmt::Debug) form instead.
+    pub fn format(&self) -> Result<String> {
+        format::format(self)
+    }
+}
+
+impl format::XcFormat for HiTime {
+    fn xc_format(&self, ctrl: *mut XC_FORMAT_CONTROL) -> xerr {
+        unsafe { rxegy_sys::xcFmtHiTime(ctrl, &self.0) }
+    }
+}
+
+impl Display for HiTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        format::display(self, f)
+    }
+}
+
+impl Sub for HiTime {
+    type Output = HiTimeDelta;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.delta_since(rhs)
+    }
+}
+
+impl From<HiTime> for SystemTime {
+    fn from(value: HiTime) -> Self {
+        value.to_system_time()
+    }
+}
+
+impl TryFrom<SystemTime> for HiTime {
+    type Error = Error;
+
+    fn try_from(value: SystemTime) -> Result<Self> {
+        let since_epoch = value
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_err(|_e| Error::TimeOutOfRange)?;
+        u64::try_from(since_epoch.as_nanos())
+            .map(Self)
+            .map_err(|_e| Error::TimeOutOfRange)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<HiTime> for chrono::DateTime<chrono::Utc> {
+    type Error = Error;
+
+    fn try_from(value: HiTime) -> Result<Self> {
+        let nanos = i64::try_from(value.0).map_err(|_e| Error::TimeOutOfRange)?;
+        Ok(chrono::DateTime::from_timestamp_nanos(nanos))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for HiTime {
+    type Error = Error;
+
+    fn try_from(value: chrono::DateTime<chrono::Utc>) -> Result<Self> {
+        value
+            .timestamp_nanos_opt()
+            .and_then(|nanos| u64::try_from(nanos).ok())
+            .map(Self)
+            .ok_or(Error::TimeOutOfRange)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<HiTime> for time::OffsetDateTime {
+    type Error = Error;
+
+    fn try_from(value: HiTime) -> Result<Self> {
+        time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(value.0))
+            .map_err(|_e| Error::TimeOutOfRange)
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<time::OffsetDateTime> for HiTime {
+    type Error = Error;
+
+    fn try_from(value: time::OffsetDateTime) -> Result<Self> {
+        u64::try_from(value.unix_timestamp_nanos())
+            .map(Self)
+            .map_err(|_e| Error::TimeOutOfRange)
+    }
+}
+
+/// A signed difference between two hi-resolution timestamps, in nanoseconds
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct HiTimeDelta(i64);
+
+impl HiTimeDelta {
+    /// Retrieve the difference in nanoseconds.
+    pub fn as_nanos(&self) -> i64 {
+        self.0
+    }
+
+    /// Convert the difference to a duration, if it is not negative.
+    pub fn to_duration(&self) -> Option<Duration> {
+        u64::try_from(self.0).ok().map(Duration::from_nanos)
+    }
+}
+
+impl From<i64> for HiTimeDelta {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<HiTimeDelta> for i64 {
+    fn from(value: HiTimeDelta) -> Self {
+        value.0
+    }
+}
+
+impl HiTimeDelta {
+    /// Format the delta with XCAPI, returning any error it reports.
+    ///
+    /// This wraps [xcFmtHiTimeDelta](rxegy_sys::xcFmtHiTimeDelta). [`Display`] falls back to the
+    /// [`Debug`](std::fmt::Debug) form instead.
+    pub fn format(&self) -> Result<String> {
+        format::format(self)
+    }
+}
+
+impl format::XcFormat for HiTimeDelta {
+    fn xc_format(&self, ctrl: *mut XC_FORMAT_CONTROL) -> xerr {
+        unsafe { rxegy_sys::xcFmtHiTimeDelta(ctrl, &self.0) }
+    }
+}
+
+impl Display for HiTimeDelta {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        format::display(self, f)
+    }
+}
+
+/// An XCAPI whole-second time
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct Time(i32);
+
+impl Time {
+    /// Convert the time to a hi-resolution timestamp.
+    pub fn to_hitime(&self) -> Result<HiTime> {
+        let mut out = 0;
+        Success::try_from(unsafe { rxegy_sys::xcCvtTimeToHiTime(&self.0, &mut out) })?;
+        Ok(HiTime(out))
+    }
+
+    /// Convert the time to an exchange-local XCAPI time.
+    pub fn to_extime(&self) -> Result<ExTime> {
+        let mut out = 0;
+        Success::try_from(unsafe { rxegy_sys::xcCvtTimeToExTime(&self.0, &mut out) })?;
+        Ok(ExTime(out))
+    }
+}
+
+impl From<i32> for Time {
+    fn from(value: i32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Time> for i32 {
+    fn from(value: Time) -> Self {
+        value.0
+    }
+}
+
+impl Time {
+    /// Format the time with XCAPI, returning any error it reports.
+    ///
+    /// This wraps [xcFmtTime](rxegy_sys::xcFmtTime). [`Display`] falls back to the
+    /// [`Debug`](std::fmt::Debug) form instead.
+    pub fn format(&self) -> Result<String> {
+        format::format(self)
+    }
+}
+
+impl format::XcFormat for Time {
+    fn xc_format(&self, ctrl: *mut XC_FORMAT_CONTROL) -> xerr {
+        unsafe { rxegy_sys::xcFmtTime(ctrl, &self.0) }
+    }
+}
+
+impl Display for Time {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        format::display(self, f)
+    }
+}
+
+/// An XCAPI exchange-local time, as published by an exchange
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct ExTime(i64);
+
+impl ExTime {
+    /// Convert the time to a hi-resolution timestamp, given the exchange's offset from UTC.
+    pub fn to_hitime(&self, timezone_offset: i32) -> Result<HiTime> {
+        let mut out = 0;
+        Success::try_from(unsafe {
+            rxegy_sys::xcCvtExTimeToHiTime(&self.0, timezone_offset, &mut out)
+        })?;
+        Ok(HiTime(out))
+    }
+
+    /// Convert the time to a whole-second XCAPI time.
+    pub fn to_time(&self) -> Result<Time> {
+        let mut out = 0;
+        Success::try_from(unsafe { rxegy_sys::xcCvtExTimeToTime(&self.0, &mut out) })?;
+        Ok(Time(out))
+    }
+}
+
+impl From<i64> for ExTime {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ExTime> for i64 {
+    fn from(value: ExTime) -> Self {
+        value.0
+    }
+}
+
+impl ExTime {
+    /// Format the time with XCAPI, returning any error it reports.
+    ///
+    /// This wraps [xcFmtExTime](rxegy_sys::xcFmtExTime). [`Display`] falls back to the
+    /// [`Debug`](std::fmt::Debug) form instead.
+    pub fn format(&self) -> Result<String> {
+        format::format(self)
+    }
+}
+
+impl format::XcFormat for ExTime {
+    fn xc_format(&self, ctrl: *mut XC_FORMAT_CONTROL) -> xerr {
+        unsafe { rxegy_sys::xcFmtExTime(ctrl, &self.0) }
+    }
+}
+
+impl Display for ExTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        format::display(self, f)
+    }
+}
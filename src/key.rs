@@ -1,19 +1,24 @@
 //! Exegy key support
 
 use crate::{
-    error::{Error, Result, Success},
+    error::{Error, Result},
     feed::Id as FeedId,
+    format,
     group::{Group, Id as GroupId},
 };
 use ref_cast::RefCast;
-use rxegy_sys::{XC_ALTERNATE_ID, XC_FORMAT_CONTROL, XC_KEY, XC_SYMBOL};
+use rxegy_sys::{
+    XC_ALTERNATE_ID, XC_FORMAT_CONTROL, XC_KEY, XC_KEY_DESC, XC_MMID, XC_SYMBOL, xerr,
+};
 use std::{
-    ffi::CStr,
-    fmt::{Display, Error as FmtError, Formatter, Result as FmtResult},
+    ffi::c_char,
+    fmt::{Display, Formatter, Result as FmtResult},
+    slice,
+    str::{self, FromStr},
 };
 
 /// A wrapper for an Exegy key
-#[derive(Clone, Eq, Hash, PartialEq, PartialOrd, Ord, RefCast)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, RefCast)]
 #[repr(transparent)]
 pub struct Key(XC_KEY);
 
@@ -23,6 +28,31 @@ impl Key {
         Key(inner)
     }
 
+    /// Create a key from a feed, feed group and symbol
+    pub fn from_parts(feed: FeedId, group: GroupId, symbol: Symbol) -> Key {
+        Key(XC_KEY {
+            xk_exchange: feed.inner(),
+            xk_country: group.inner(),
+            xk_symbol: symbol.0,
+        })
+    }
+
+    /// Parse a key from its string form, e.g. `AAPL.US.US`
+    ///
+    /// This wraps [xcParseKey](rxegy_sys::xcParseKey). Keys whose symbols are too long for an
+    /// [`XC_KEY`](rxegy_sys::XC_KEY) cannot be parsed, and must be subscribed by string instead.
+    pub fn parse(s: &str) -> Result<Key> {
+        format::parse_with(s, XC_KEY::default(), |inp, endptr, out| unsafe {
+            rxegy_sys::xcParseKey(inp, endptr, out)
+        })
+        .map(Key)
+    }
+
+    /// Retrieve the raw key
+    pub(crate) fn inner(&self) -> XC_KEY {
+        self.0
+    }
+
     /// Retrieve a reference to the exchange ID in this key
     pub fn feed_id(&self) -> &FeedId {
         FeedId::ref_cast(&self.0.xk_exchange)
@@ -44,51 +74,162 @@ impl Key {
     }
 }
 
+impl From<(FeedId, GroupId, Symbol)> for Key {
+    fn from((feed, group, symbol): (FeedId, GroupId, Symbol)) -> Self {
+        Key::from_parts(feed, group, symbol)
+    }
+}
+
+impl Key {
+    /// Format the key with XCAPI, returning any error it reports.
+    ///
+    /// This wraps [xcFmtKey](rxegy_sys::xcFmtKey). [`Display`] falls back to the
+    /// [`Debug`](std::fmt::Debug) form instead.
+    pub fn format(&self) -> Result<String> {
+        format::format(self)
+    }
+}
+
+impl format::XcFormat for Key {
+    fn xc_format(&self, ctrl: *mut XC_FORMAT_CONTROL) -> xerr {
+        unsafe { rxegy_sys::xcFmtKey(ctrl, &self.0) }
+    }
+}
+
 impl Display for Key {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        let mut outbuf = [0i8; 81];
-        let mut ctrl = XC_FORMAT_CONTROL::default();
-        let status = unsafe {
-            rxegy_sys::xcFmtInitialize(&mut ctrl, outbuf.as_mut_ptr(), outbuf.len() as u32)
+        format::display(self, f)
+    }
+}
+
+impl FromStr for Key {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Key::parse(s)
+    }
+}
+
+/// A key along with the namespace its symbol belongs to
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord, RefCast)]
+#[repr(transparent)]
+pub struct KeyDesc(XC_KEY_DESC);
+
+impl KeyDesc {
+    /// Create a key description for a key in the given symbol namespace
+    pub fn new(key: Key, symbol_namespace: u8) -> KeyDesc {
+        let mut inner = XC_KEY_DESC {
+            xkd_key: key.0,
+            ..Default::default()
         };
+        inner.set_xkd_symbol_namespace(symbol_namespace);
+        KeyDesc(inner)
+    }
 
-        Success::try_from(status).map_err(|_e| FmtError)?;
+    /// Parse a key description from its string form
+    ///
+    /// This wraps [xcParseKeyDesc](rxegy_sys::xcParseKeyDesc).
+    pub fn parse(s: &str) -> Result<KeyDesc> {
+        format::parse_with(s, XC_KEY_DESC::default(), |inp, endptr, out| unsafe {
+            rxegy_sys::xcParseKeyDesc(inp, endptr, out)
+        })
+        .map(KeyDesc)
+    }
 
-        let status = unsafe { rxegy_sys::xcFmtKey(&mut ctrl, &self.0) };
+    /// Retrieve a reference to the key
+    pub fn key(&self) -> &Key {
+        Key::ref_cast(&self.0.xkd_key)
+    }
 
-        Success::try_from(status).map_err(|_e| FmtError)?;
+    /// Retrieve the namespace the symbol belongs to
+    pub fn symbol_namespace(&self) -> u8 {
+        self.0.xkd_symbol_namespace()
+    }
+}
 
-        let outbuf = unsafe { CStr::from_ptr(outbuf.as_mut_ptr()) };
+impl KeyDesc {
+    /// Format the key description with XCAPI, returning any error it reports.
+    ///
+    /// This wraps [xcFmtKeyDesc](rxegy_sys::xcFmtKeyDesc). [`Display`] falls back to the
+    /// [`Debug`](std::fmt::Debug) form instead.
+    pub fn format(&self) -> Result<String> {
+        format::format(self)
+    }
+}
 
-        write!(f, "{}", outbuf.to_str().map_err(|_e| FmtError)?)
+impl format::XcFormat for KeyDesc {
+    fn xc_format(&self, ctrl: *mut XC_FORMAT_CONTROL) -> xerr {
+        unsafe { rxegy_sys::xcFmtKeyDesc(ctrl, &self.0) }
+    }
+}
+
+impl Display for KeyDesc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        format::display(self, f)
+    }
+}
+
+impl FromStr for KeyDesc {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        KeyDesc::parse(s)
     }
 }
 
 /// Exegy symbol data.
-#[derive(Clone, Eq, Hash, PartialEq, PartialOrd, Ord, RefCast)]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord, RefCast)]
 #[repr(transparent)]
 pub struct Symbol(XC_SYMBOL);
 
-impl Display for Symbol {
-    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        let mut outbuf = [0i8; 81];
-        let mut ctrl = XC_FORMAT_CONTROL::default();
-        let status = unsafe {
-            rxegy_sys::xcFmtInitialize(&mut ctrl, outbuf.as_mut_ptr(), outbuf.len() as u32)
-        };
+impl Symbol {
+    /// Parse a symbol from its string form
+    ///
+    /// This wraps [xcParseSymbol](rxegy_sys::xcParseSymbol).
+    pub fn parse(s: &str) -> Result<Symbol> {
+        format::parse_with(s, XC_SYMBOL::default(), |inp, endptr, out| unsafe {
+            rxegy_sys::xcParseSymbol(inp, endptr, out)
+        })
+        .map(Symbol)
+    }
 
-        Success::try_from(status).map_err(|_e| FmtError)?;
+    /// Retrieve the symbol characters, without any trailing padding
+    pub fn as_str(&self) -> Result<&str> {
+        chars_as_str(&self.0.xsym_ch)
+    }
+}
+
+impl Symbol {
+    /// Format the symbol with XCAPI, returning any error it reports.
+    ///
+    /// This wraps [xcFmtSymbol](rxegy_sys::xcFmtSymbol). [`Display`] falls back to the
+    /// [`Debug`](std::fmt::Debug) form instead.
+    pub fn format(&self) -> Result<String> {
+        format::format(self)
+    }
+}
 
-        let status = unsafe { rxegy_sys::xcFmtSymbol(&mut ctrl, &self.0) };
+impl format::XcFormat for Symbol {
+    fn xc_format(&self, ctrl: *mut XC_FORMAT_CONTROL) -> xerr {
+        unsafe { rxegy_sys::xcFmtSymbol(ctrl, &self.0) }
+    }
+}
 
-        Success::try_from(status).map_err(|_e| FmtError)?;
+impl Display for Symbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        format::display(self, f)
+    }
+}
 
-        let outbuf = unsafe { CStr::from_ptr(outbuf.as_mut_ptr()) };
+impl FromStr for Symbol {
+    type Err = Error;
 
-        write!(f, "{}", outbuf.to_str().map_err(|_e| FmtError)?)
+    fn from_str(s: &str) -> Result<Self> {
+        Symbol::parse(s)
     }
 }
 
+/// An alternate instrument identifier, such as a CUSIP or ISIN
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, RefCast)]
 #[repr(transparent)]
 pub struct AlternateId(XC_ALTERNATE_ID);
@@ -98,4 +239,252 @@ impl AlternateId {
     pub(crate) fn new(inner: XC_ALTERNATE_ID) -> Self {
         AlternateId(inner)
     }
+
+    /// Parse an alternate ID from its string form
+    ///
+    /// This wraps [xcParseAlternateId](rxegy_sys::xcParseAlternateId).
+    pub fn parse(s: &str) -> Result<AlternateId> {
+        format::parse_with(s, XC_ALTERNATE_ID::default(), |inp, endptr, out| unsafe {
+            rxegy_sys::xcParseAlternateId(inp, endptr, out)
+        })
+        .map(AlternateId)
+    }
+
+    /// Retrieve the identifier characters, without any trailing padding
+    pub fn as_str(&self) -> Result<&str> {
+        chars_as_str(&self.0.xalt_ch)
+    }
+}
+
+impl AlternateId {
+    /// Format the alternate ID with XCAPI, returning any error it reports.
+    ///
+    /// This wraps [xcFmtAlternateId](rxegy_sys::xcFmtAlternateId). [`Display`] falls back to the
+    /// [`Debug`](std::fmt::Debug) form instead.
+    pub fn format(&self) -> Result<String> {
+        format::format(self)
+    }
+}
+
+impl format::XcFormat for AlternateId {
+    fn xc_format(&self, ctrl: *mut XC_FORMAT_CONTROL) -> xerr {
+        unsafe { rxegy_sys::xcFmtAlternateId(ctrl, &self.0) }
+    }
+}
+
+impl Display for AlternateId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        format::display(self, f)
+    }
+}
+
+impl FromStr for AlternateId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        AlternateId::parse(s)
+    }
+}
+
+/// A market maker identifier
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, RefCast)]
+#[repr(transparent)]
+pub struct Mmid(XC_MMID);
+
+impl Mmid {
+    /// Parse a market maker ID from its string form
+    ///
+    /// This wraps [xcParseMMID](rxegy_sys::xcParseMMID).
+    pub fn parse(s: &str) -> Result<Mmid> {
+        format::parse_with(s, XC_MMID::default(), |inp, endptr, out| unsafe {
+            rxegy_sys::xcParseMMID(inp, endptr, out)
+        })
+        .map(Mmid)
+    }
+
+    /// Retrieve the identifier characters, without any trailing padding
+    pub fn as_str(&self) -> Result<&str> {
+        chars_as_str(&self.0.xmmid_ch)
+    }
+}
+
+impl Mmid {
+    /// Format the market maker ID with XCAPI, returning any error it reports.
+    ///
+    /// This wraps [xcFmtMMID](rxegy_sys::xcFmtMMID). [`Display`] falls back to the
+    /// [`Debug`](std::fmt::Debug) form instead.
+    pub fn format(&self) -> Result<String> {
+        format::format(self)
+    }
+}
+
+impl format::XcFormat for Mmid {
+    fn xc_format(&self, ctrl: *mut XC_FORMAT_CONTROL) -> xerr {
+        unsafe { rxegy_sys::xcFmtMMID(ctrl, &self.0) }
+    }
+}
+
+impl Display for Mmid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        format::display(self, f)
+    }
+}
+
+impl FromStr for Mmid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Mmid::parse(s)
+    }
+}
+
+/// Read a fixed-width XCAPI character array, which is nul-padded but not nul-terminated when full.
+fn chars_as_str(chars: &[c_char]) -> Result<&str> {
+    let bytes = unsafe { slice::from_raw_parts(chars.as_ptr().cast::<u8>(), chars.len()) };
+    let len = bytes.iter().position(|&ch| ch == 0).unwrap_or(bytes.len());
+
+    Ok(str::from_utf8(&bytes[..len])?)
+}
+
+/// Serialize a type with its XCAPI formatting function, and deserialize with its parsing function.
+#[cfg(feature = "serde")]
+macro_rules! impl_serde_via_str {
+    ($($ty:ty => $expecting:literal;)+) => {$(
+        impl serde::Serialize for $ty {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> ::std::result::Result<S::Ok, S::Error> {
+                // The Display fallback would not parse back, so surface the XCAPI error instead
+                let formatted = self.format().map_err(serde::ser::Error::custom)?;
+                serializer.serialize_str(&formatted)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $ty {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> ::std::result::Result<Self, D::Error> {
+                struct Visitor;
+
+                impl serde::de::Visitor<'_> for Visitor {
+                    type Value = $ty;
+
+                    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+                        f.write_str($expecting)
+                    }
+
+                    fn visit_str<E: serde::de::Error>(
+                        self,
+                        v: &str,
+                    ) -> ::std::result::Result<$ty, E> {
+                        v.parse().map_err(E::custom)
+                    }
+                }
+
+                deserializer.deserialize_str(Visitor)
+            }
+        }
+    )+};
+}
+
+#[cfg(feature = "serde")]
+impl_serde_via_str! {
+    Symbol => "an Exegy symbol";
+    AlternateId => "an Exegy alternate instrument ID";
+    Mmid => "an Exegy market maker ID";
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Copy an ASCII string into a fixed-width XCAPI character array, nul-padding the rest.
+    fn chars<const N: usize>(s: &str) -> [c_char; N] {
+        let mut chars = [0; N];
+        for (ch, &byte) in chars.iter_mut().zip(s.as_bytes()) {
+            *ch = byte as c_char;
+        }
+        chars
+    }
+
+    #[test]
+    fn padded_chars() {
+        assert_eq!(chars_as_str(&chars::<8>("IBM")).unwrap(), "IBM");
+        assert_eq!(chars_as_str(&chars::<8>("")).unwrap(), "");
+    }
+
+    #[test]
+    fn full_width_chars() {
+        // A full array has no nul terminator, so the whole array is the string
+        let symbol = Symbol(XC_SYMBOL {
+            xsym_ch: chars("ABCDEFGHIJKLMNOPQRST"),
+        });
+        assert_eq!(symbol.as_str().unwrap(), "ABCDEFGHIJKLMNOPQRST");
+
+        let alternate = AlternateId::new(XC_ALTERNATE_ID {
+            xalt_ch: chars("US0378331005ABCD"),
+        });
+        assert_eq!(alternate.as_str().unwrap(), "US0378331005ABCD");
+
+        let mmid = Mmid(XC_MMID {
+            xmmid_ch: chars("GSCOXX"),
+        });
+        assert_eq!(mmid.as_str().unwrap(), "GSCOXX");
+    }
+
+    #[test]
+    fn chars_after_nul_are_ignored() {
+        let mut raw = chars::<8>("AB");
+        raw[3] = b'Z' as c_char;
+        assert_eq!(chars_as_str(&raw).unwrap(), "AB");
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let mut raw = chars::<4>("A");
+        raw[1] = 0xffu8 as c_char;
+        assert!(matches!(chars_as_str(&raw), Err(Error::InvalidUtf8(_))));
+    }
+
+    #[cfg(feature = "serde")]
+    mod serialize {
+        use super::*;
+        use serde_test::{Token, assert_de_tokens_error, assert_tokens};
+
+        #[test]
+        fn symbol_round_trip() {
+            let symbol = Symbol::parse("AAPL").unwrap();
+            assert_eq!(symbol.as_str().unwrap(), "AAPL");
+            assert_tokens(&symbol, &[Token::Str("AAPL")]);
+        }
+
+        #[test]
+        fn mmid_round_trip() {
+            let mmid = Mmid::parse("GSCO").unwrap();
+            assert_tokens(&mmid, &[Token::Str("GSCO")]);
+        }
+
+        #[test]
+        fn alternate_id_round_trip() {
+            let alternate = AlternateId::parse("US0378331005").unwrap();
+            assert_tokens(&alternate, &[Token::Str("US0378331005")]);
+        }
+
+        #[test]
+        fn expects_a_string() {
+            assert_de_tokens_error::<Symbol>(
+                &[Token::U32(1)],
+                "invalid type: integer `1`, expected an Exegy symbol",
+            );
+            assert_de_tokens_error::<AlternateId>(
+                &[Token::Bool(true)],
+                "invalid type: boolean `true`, expected an Exegy alternate instrument ID",
+            );
+            assert_de_tokens_error::<Mmid>(
+                &[Token::Unit],
+                "invalid type: unit value, expected an Exegy market maker ID",
+            );
+        }
+    }
 }
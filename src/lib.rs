@@ -1,29 +1,48 @@
 //! Unofficial Exegy Rust Bindings
 
 pub use self::{
+    date::{Date, DateTime, DaysBack},
     error::{Error, ExegyError, Result, Success},
     feed::{Feed, Id as FeedId, Internal as InternalFeed, Us as UsFeed},
+    field_set::{AttachedFieldSet, FieldSet, FieldSetLayout, FieldSetMember, FieldValue},
     group::{Corporate, Country, Group, Id as GroupId},
-    key::{AlternateId, Key, Symbol},
-    misc::{Currency, Date, HiTime, OrderRefIdKind, Size, SymbolKind, TradeVenue, Volume},
-    price::{ExponentKind, Price, PriceKind, format_price_string},
+    hitime::{ExTime, HiTime, HiTimeDelta, Time},
+    key::{AlternateId, Key, KeyDesc, Mmid, Symbol},
+    misc::{Currency, Size, SymbolKind, TradeVenue, Volume},
+    order_ref::{OrderRefId, OrderRefIdKind, OrderRefIdValue},
+    price::{
+        DFloat, ExponentKind, Price, PriceDesc, PriceKind, PriceValue, TradedValue,
+        format_price_string,
+    },
+    qualifier::{
+        QuoteQualifier, QuoteQualifiers, RefreshQualifier, RefreshQualifiers, TickFlags,
+        TradeQualifier, TradeQualifiers, TradeUpdateFlags,
+    },
     status::{Instrument as InstrumentStatus, Market as MarketStatus},
-    timing::EventTiming,
+    timing::{EventTiming, LatencyBreakdown},
 };
 
 pub mod container;
 pub mod event;
 pub mod object;
 pub mod session;
+#[cfg(feature = "stats")]
+pub mod stats;
 
+mod date;
 mod error;
 mod feed;
 mod field;
+mod field_set;
+mod format;
 mod group;
+mod hitime;
 mod key;
 mod line;
 mod macros;
 mod misc;
+mod order_ref;
 mod price;
+mod qualifier;
 mod status;
 mod timing;
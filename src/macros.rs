@@ -17,3 +17,22 @@ macro_rules! impl_wrapper_on_newtype {
         }
     };
 }
+
+// Implement the wrapper for a newtype which may own the object it wraps, and is generic over the
+// type of user data given to its slots
+#[macro_export]
+macro_rules! impl_wrapper_on_owned_newtype {
+    ($name:ident, $kind:expr) => {
+        impl<U: 'static> $crate::object::Wrapper for $name<U> {
+            const KIND: $crate::object::Kind = $kind;
+
+            fn from_ptr_unchecked(ptr: ::std::ptr::NonNull<::std::ffi::c_void>) -> Self {
+                Self(ptr, None, ::std::marker::PhantomData)
+            }
+
+            fn as_xhandle(&self) -> ::rxegy_sys::xhandle {
+                self.0.as_ptr()
+            }
+        }
+    };
+}
@@ -1,6 +1,5 @@
-use crate::Error;
 use ref_cast::RefCast;
-use rxegy_sys::{XC_CURRENCY_ID, XC_DATE, XC_TRADE_VENUE};
+use rxegy_sys::{XC_CURRENCY_ID, XC_TRADE_VENUE};
 
 /// A currency ID
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, RefCast)]
@@ -23,87 +22,6 @@ impl From<Currency> for [u8; 3] {
     }
 }
 
-/// A date
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, RefCast)]
-#[repr(transparent)]
-pub struct Date(XC_DATE);
-
-impl Date {
-    pub(crate) fn new(inner: XC_DATE) -> Self {
-        Self(inner)
-    }
-
-    pub fn year(&self) -> u32 {
-        unsafe { self.0.__bindgen_anon_1 }.xdt_year()
-    }
-
-    pub fn month(&self) -> u32 {
-        unsafe { self.0.__bindgen_anon_1 }.xdt_month()
-    }
-
-    pub fn day(&self) -> u32 {
-        unsafe { self.0.__bindgen_anon_1 }.xdt_day()
-    }
-}
-
-impl From<&Date> for u32 {
-    fn from(value: &Date) -> Self {
-        unsafe { value.0.xdt_raw }
-    }
-}
-
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
-#[repr(transparent)]
-pub struct HiTime(u64);
-
-impl From<u64> for HiTime {
-    #[inline(always)]
-    fn from(value: u64) -> Self {
-        Self(value)
-    }
-}
-
-impl From<HiTime> for u64 {
-    #[inline(always)]
-    fn from(value: HiTime) -> Self {
-        value.0
-    }
-}
-
-/// An enumeration of order reference ID encodings
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-#[repr(u8)]
-pub enum OrderRefIdKind {
-    /// 7-bit ASCII
-    Ascii = rxegy_sys::XOIDT_ASCII,
-    /// Binary-coded decimal (4 bits per decimal digit)
-    BinaryCodedDecimal = rxegy_sys::XOIDT_BCD,
-    /// Pair of unsigned 32-bit integers
-    UintPair = rxegy_sys::XOIDT_UINT_PAIR,
-    /// 12-character alphanumeric format encoded as a base-36 number
-    Alpha32 = rxegy_sys::XOIDT_ALPHA36,
-    /// A single unsigned 64-bit integer
-    Raw64 = rxegy_sys::XOIDT_RAW64,
-    /// Union of instrument ID and binary ref number -- disambiguates per-instrument reference IDs
-    SymIdUint = rxegy_sys::XOIDT_SYMID_UINT,
-}
-
-impl TryFrom<u8> for OrderRefIdKind {
-    type Error = Error;
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            rxegy_sys::XOIDT_ASCII => Ok(Self::Ascii),
-            rxegy_sys::XOIDT_BCD => Ok(Self::BinaryCodedDecimal),
-            rxegy_sys::XOIDT_UINT_PAIR => Ok(Self::UintPair),
-            rxegy_sys::XOIDT_ALPHA36 => Ok(Self::Alpha32),
-            rxegy_sys::XOIDT_RAW64 => Ok(Self::Raw64),
-            rxegy_sys::XOIDT_SYMID_UINT => Ok(Self::SymIdUint),
-            _ => Err(Error::KindUnknown),
-        }
-    }
-}
-
 /// A value suitable for representing the size associated with a trade or quote
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(transparent)]
@@ -0,0 +1,386 @@
+//! Exegy Order Reference IDs
+
+use crate::{
+    error::{Error, Result},
+    format,
+};
+use rxegy_sys::{XC_FORMAT_CONTROL, XC_ORDER_REF_ID, xerr};
+use std::{
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
+    mem, str,
+};
+
+/// An enumeration of order reference ID encodings
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(u8)]
+pub enum OrderRefIdKind {
+    /// 7-bit ASCII
+    Ascii = rxegy_sys::XOIDT_ASCII,
+    /// Binary-coded decimal (4 bits per decimal digit)
+    BinaryCodedDecimal = rxegy_sys::XOIDT_BCD,
+    /// Pair of unsigned 32-bit integers
+    UintPair = rxegy_sys::XOIDT_UINT_PAIR,
+    /// 12-character alphanumeric format encoded as a base-36 number
+    Alpha36 = rxegy_sys::XOIDT_ALPHA36,
+    /// A single unsigned 64-bit integer
+    Raw64 = rxegy_sys::XOIDT_RAW64,
+    /// Union of instrument ID and binary ref number -- disambiguates per-instrument reference IDs
+    SymIdUint = rxegy_sys::XOIDT_SYMID_UINT,
+}
+
+impl OrderRefIdKind {
+    /// The former name of [`OrderRefIdKind::Alpha36`]
+    #[deprecated(note = "renamed to `OrderRefIdKind::Alpha36`")]
+    #[allow(non_upper_case_globals)]
+    pub const Alpha32: Self = Self::Alpha36;
+}
+
+impl TryFrom<u8> for OrderRefIdKind {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            rxegy_sys::XOIDT_ASCII => Ok(Self::Ascii),
+            rxegy_sys::XOIDT_BCD => Ok(Self::BinaryCodedDecimal),
+            rxegy_sys::XOIDT_UINT_PAIR => Ok(Self::UintPair),
+            rxegy_sys::XOIDT_ALPHA36 => Ok(Self::Alpha36),
+            rxegy_sys::XOIDT_RAW64 => Ok(Self::Raw64),
+            rxegy_sys::XOIDT_SYMID_UINT => Ok(Self::SymIdUint),
+            _ => Err(Error::KindUnknown),
+        }
+    }
+}
+
+/// An order reference ID, along with the encoding it should be read with
+///
+/// XCAPI does not store the encoding in the ID itself; it is published per instrument, and
+/// alongside each event which carries order references. Two IDs are equal when they have the same
+/// encoding and the same value in that encoding, so bytes the encoding does not use are ignored.
+#[derive(Clone, Copy)]
+pub struct OrderRefId {
+    inner: XC_ORDER_REF_ID,
+    kind: Option<OrderRefIdKind>,
+}
+
+impl OrderRefId {
+    #[inline(always)]
+    pub(crate) fn new(inner: XC_ORDER_REF_ID, kind: OrderRefIdKind) -> Self {
+        Self {
+            inner,
+            kind: Some(kind),
+        }
+    }
+
+    /// Parse an order reference ID in an unspecified encoding
+    ///
+    /// This wraps [xcParseOrderRefId](rxegy_sys::xcParseOrderRefId). The result can only be
+    /// decoded once its encoding is supplied with [`OrderRefId::with_kind()`].
+    pub fn parse(s: &str) -> Result<OrderRefId> {
+        format::parse_with(s, XC_ORDER_REF_ID::default(), |inp, endptr, out| unsafe {
+            rxegy_sys::xcParseOrderRefId(inp, endptr, out)
+        })
+        .map(|inner| Self { inner, kind: None })
+    }
+
+    /// Parse an order reference ID in the given encoding
+    ///
+    /// This wraps [xcParseOrderRefIdAs](rxegy_sys::xcParseOrderRefIdAs).
+    pub fn parse_as(s: &str, kind: OrderRefIdKind) -> Result<OrderRefId> {
+        format::parse_with(s, XC_ORDER_REF_ID::default(), |inp, endptr, out| unsafe {
+            rxegy_sys::xcParseOrderRefIdAs(inp, endptr, out, kind as u8)
+        })
+        .map(|inner| Self::new(inner, kind))
+    }
+
+    /// Retrieve the encoding of the ID, if it is known
+    pub fn kind(&self) -> Option<OrderRefIdKind> {
+        self.kind
+    }
+
+    /// Reinterpret the ID with the given encoding
+    pub fn with_kind(self, kind: OrderRefIdKind) -> OrderRefId {
+        Self::new(self.inner, kind)
+    }
+
+    /// Decode the ID according to its encoding
+    pub fn decode(&self) -> Result<OrderRefIdValue<'_>> {
+        let kind = self.kind.ok_or(Error::KindUnknown)?;
+
+        // Every member of the union is plain data, so reading any of them is sound
+        let value = unsafe {
+            match kind {
+                OrderRefIdKind::Ascii => OrderRefIdValue::Ascii(str::from_utf8(self.ascii())?),
+                OrderRefIdKind::BinaryCodedDecimal => {
+                    OrderRefIdValue::BinaryCodedDecimal(self.inner.__bindgen_anon_2.xoid_bcd_pair)
+                }
+                OrderRefIdKind::UintPair => {
+                    let pair = self.inner.__bindgen_anon_3;
+                    OrderRefIdValue::UintPair(pair.xoid_uint1, pair.xoid_uint2)
+                }
+                OrderRefIdKind::Alpha36 => {
+                    OrderRefIdValue::Alpha36(self.inner.__bindgen_anon_4.xoid_alpha36)
+                }
+                OrderRefIdKind::Raw64 => {
+                    OrderRefIdValue::Raw64(self.inner.__bindgen_anon_5.xoid_raw64)
+                }
+                OrderRefIdKind::SymIdUint => {
+                    let pair = self.inner.__bindgen_anon_6;
+                    OrderRefIdValue::SymIdUint {
+                        symbol_id: pair.xoid_sym_uint_symbol,
+                        ref_id: pair.xoid_sym_uint_refid,
+                    }
+                }
+            }
+        };
+
+        Ok(value)
+    }
+
+    /// The raw bytes of the ID.
+    fn bytes(&self) -> &[u8; mem::size_of::<XC_ORDER_REF_ID>()] {
+        unsafe { &*(&self.inner as *const XC_ORDER_REF_ID).cast() }
+    }
+
+    /// The characters of an ASCII ID, which is nul-padded but not nul-terminated when full.
+    fn ascii(&self) -> &[u8] {
+        let bytes = self.bytes();
+        let len = bytes.iter().position(|&ch| ch == 0).unwrap_or(bytes.len());
+        &bytes[..len]
+    }
+
+    /// The bytes which hold the value in the ID's encoding.
+    fn significant_bytes(&self) -> &[u8] {
+        match self.kind {
+            Some(OrderRefIdKind::Ascii) => self.ascii(),
+            Some(OrderRefIdKind::UintPair | OrderRefIdKind::Alpha36 | OrderRefIdKind::Raw64) => {
+                &self.bytes()[..mem::size_of::<u64>()]
+            }
+            Some(OrderRefIdKind::BinaryCodedDecimal | OrderRefIdKind::SymIdUint) | None => {
+                self.bytes()
+            }
+        }
+    }
+}
+
+impl PartialEq for OrderRefId {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.significant_bytes() == other.significant_bytes()
+    }
+}
+
+impl Eq for OrderRefId {}
+
+impl Hash for OrderRefId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind.hash(state);
+        self.significant_bytes().hash(state);
+    }
+}
+
+impl Debug for OrderRefId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.decode() {
+            Ok(value) => f.debug_tuple("OrderRefId").field(&value).finish(),
+            Err(_e) => f
+                .debug_struct("OrderRefId")
+                .field("kind", &self.kind)
+                .field("bytes", self.bytes())
+                .finish(),
+        }
+    }
+}
+
+impl OrderRefId {
+    /// Format the order reference ID with XCAPI, returning any error it reports.
+    ///
+    /// This wraps [xcFmtOrderRefIdAs](rxegy_sys::xcFmtOrderRefIdAs) when the encoding is known,
+    /// and [xcFmtOrderRefId](rxegy_sys::xcFmtOrderRefId) otherwise. [`Display`] falls back to the
+    /// [`Debug`](std::fmt::Debug) form instead.
+    pub fn format(&self) -> Result<String> {
+        format::format(self)
+    }
+}
+
+impl format::XcFormat for OrderRefId {
+    fn xc_format(&self, ctrl: *mut XC_FORMAT_CONTROL) -> xerr {
+        unsafe {
+            match self.kind {
+                Some(kind) => rxegy_sys::xcFmtOrderRefIdAs(ctrl, &self.inner, kind as u8),
+                None => rxegy_sys::xcFmtOrderRefId(ctrl, &self.inner),
+            }
+        }
+    }
+}
+
+impl Display for OrderRefId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        format::display(self, f)
+    }
+}
+
+/// A decoded order reference ID
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum OrderRefIdValue<'a> {
+    /// 7-bit ASCII characters, without trailing padding
+    Ascii(&'a str),
+    /// Binary-coded decimal digits, two per byte
+    BinaryCodedDecimal([u8; 12]),
+    /// A pair of unsigned 32-bit integers
+    UintPair(u32, u32),
+    /// A 12-character alphanumeric ID, encoded as a base-36 number
+    Alpha36(u64),
+    /// A single unsigned 64-bit integer
+    Raw64(u64),
+    /// A reference number, disambiguated by the ID of the instrument it belongs to
+    SymIdUint {
+        /// The instrument's symbol hash ID
+        symbol_id: u32,
+        /// The reference number within the instrument
+        ref_id: u64,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rxegy_sys::XC_ORDER_REF_ID__bindgen_ty_2;
+    use std::hash::DefaultHasher;
+
+    const KINDS: [OrderRefIdKind; 6] = [
+        OrderRefIdKind::Ascii,
+        OrderRefIdKind::BinaryCodedDecimal,
+        OrderRefIdKind::UintPair,
+        OrderRefIdKind::Alpha36,
+        OrderRefIdKind::Raw64,
+        OrderRefIdKind::SymIdUint,
+    ];
+
+    fn inner(bytes: [u8; 12]) -> XC_ORDER_REF_ID {
+        XC_ORDER_REF_ID {
+            __bindgen_anon_2: XC_ORDER_REF_ID__bindgen_ty_2 {
+                xoid_bcd_pair: bytes,
+            },
+        }
+    }
+
+    fn hash(id: &OrderRefId) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        id.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn assert_same(a: OrderRefId, b: OrderRefId) {
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b), "{a:?} and {b:?} hash differently");
+    }
+
+    #[test]
+    fn default_is_zeroed() {
+        assert_eq!(
+            OrderRefId::new(XC_ORDER_REF_ID::default(), OrderRefIdKind::Raw64),
+            OrderRefId::new(inner([0; 12]), OrderRefIdKind::Raw64)
+        );
+    }
+
+    #[test]
+    fn ascii_ignores_padding() {
+        let kind = OrderRefIdKind::Ascii;
+        let padded = OrderRefId::new(inner(*b"ABC\0\0\0\0\0\0\0\0\0"), kind);
+        let garbage = OrderRefId::new(inner(*b"ABC\0junkjunk"), kind);
+        assert_same(padded, garbage);
+        assert_eq!(padded.decode().unwrap(), OrderRefIdValue::Ascii("ABC"));
+
+        let longer = OrderRefId::new(inner(*b"ABCD\0\0\0\0\0\0\0\0"), kind);
+        assert_ne!(padded, longer);
+
+        let full = OrderRefId::new(inner(*b"ABCDEFGHIJKL"), kind);
+        assert_eq!(
+            full.decode().unwrap(),
+            OrderRefIdValue::Ascii("ABCDEFGHIJKL")
+        );
+        assert_ne!(full, OrderRefId::new(inner(*b"ABCDEFGHIJKM"), kind));
+    }
+
+    #[test]
+    fn eight_byte_kinds_ignore_trailing_bytes() {
+        let low = *b"\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0";
+        let mut high = low;
+        high[8..].copy_from_slice(&[0xff; 4]);
+
+        for kind in [
+            OrderRefIdKind::UintPair,
+            OrderRefIdKind::Alpha36,
+            OrderRefIdKind::Raw64,
+        ] {
+            let a = OrderRefId::new(inner(low), kind);
+            let b = OrderRefId::new(inner(high), kind);
+            assert_same(a, b);
+            assert_eq!(a.decode().unwrap(), b.decode().unwrap());
+
+            let mut other = low;
+            other[7] ^= 1;
+            assert_ne!(a, OrderRefId::new(inner(other), kind));
+        }
+    }
+
+    #[test]
+    fn twelve_byte_kinds_compare_every_byte() {
+        let low = *b"\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0";
+        let mut high = low;
+        high[11] = 1;
+
+        for kind in [
+            OrderRefIdKind::BinaryCodedDecimal,
+            OrderRefIdKind::SymIdUint,
+        ] {
+            assert_ne!(
+                OrderRefId::new(inner(low), kind),
+                OrderRefId::new(inner(high), kind)
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_kind_compares_every_byte() {
+        let unknown = |bytes| OrderRefId {
+            inner: inner(bytes),
+            kind: None,
+        };
+        let bytes = *b"ABC\0\0\0\0\0\0\0\0\0";
+        let mut trailing = bytes;
+        trailing[11] = b'x';
+
+        assert_same(unknown(bytes), unknown(bytes));
+        assert_ne!(unknown(bytes), unknown(trailing));
+        assert!(unknown(bytes).decode().is_err());
+    }
+
+    #[test]
+    fn kind_is_significant() {
+        let bytes = *b"ABCDEFGHIJKL";
+        let unknown = OrderRefId {
+            inner: inner(bytes),
+            kind: None,
+        };
+
+        for (i, &a) in KINDS.iter().enumerate() {
+            let id = OrderRefId::new(inner(bytes), a);
+            assert_ne!(id, unknown);
+            assert_same(id, unknown.with_kind(a));
+            for &b in &KINDS[i + 1..] {
+                assert_ne!(id, id.with_kind(b), "{a:?} and {b:?} compare equal");
+            }
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn alpha32_alias() {
+        assert_eq!(OrderRefIdKind::Alpha32, OrderRefIdKind::Alpha36);
+        assert_eq!(
+            OrderRefIdKind::try_from(rxegy_sys::XOIDT_ALPHA36).unwrap(),
+            OrderRefIdKind::Alpha36
+        );
+    }
+}
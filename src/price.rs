@@ -1,7 +1,18 @@
 //! Exegy Price Objects
 
-use crate::Error;
-use std::fmt::{Formatter, Result as FmtResult};
+use crate::{
+    Error, Volume,
+    error::{ExegyError, Result as XResult, Success},
+    field_set::FieldValue,
+};
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+use rxegy_sys::{XC_DFLOAT_64, XC_PRICE_DESC};
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
+};
 
 /// A price kind
 #[derive(Clone, Copy, Debug, displaydoc::Display, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -9,7 +20,7 @@ use std::fmt::{Formatter, Result as FmtResult};
 pub enum PriceKind {
     /// The price is normal: {0}
     Normal(i32),
-    /// The price is blank
+    /// The price is blank, either low or high
     Blank = rxegy_sys::XC_BLANK_PRICE,
     /// The price is a market price
     Market = rxegy_sys::XC_MARKET_PRICE,
@@ -45,6 +56,7 @@ impl Price {
     pub fn value(&self) -> Option<i32> {
         match self.0 {
             rxegy_sys::XC_BLANK_PRICE
+            | rxegy_sys::XC_BLANK_PRICE_HIGH
             | rxegy_sys::XC_MARKET_PRICE
             | rxegy_sys::XC_OVERFLOW_PRICE
             | rxegy_sys::XC_UNDERFLOW_PRICE => None,
@@ -52,10 +64,19 @@ impl Price {
         }
     }
 
+    /// Convert the price from one exponent to another, using XCAPI's rounding rules.
+    pub fn convert(&self, from: ExponentKind, to: ExponentKind) -> XResult<Price> {
+        let mut out = 0;
+        Success::try_from(unsafe {
+            rxegy_sys::xcCvtPrice(&self.0, from as u8, to as u8, &mut out)
+        })?;
+        Ok(Price(out))
+    }
+
     /// Retrieve the type of value contained in this price object
     pub fn kind(&self) -> PriceKind {
         match self.0 {
-            rxegy_sys::XC_BLANK_PRICE => PriceKind::Blank,
+            rxegy_sys::XC_BLANK_PRICE | rxegy_sys::XC_BLANK_PRICE_HIGH => PriceKind::Blank,
             rxegy_sys::XC_MARKET_PRICE => PriceKind::Market,
             rxegy_sys::XC_OVERFLOW_PRICE => PriceKind::Overflow,
             rxegy_sys::XC_UNDERFLOW_PRICE => PriceKind::Underflow,
@@ -70,31 +91,464 @@ pub fn format_price_string(
     price: Price,
     exponent_kind: ExponentKind,
 ) -> FmtResult {
-    match price.kind() {
-        PriceKind::Blank => Ok(()),
-        PriceKind::Normal(value) => {
-            let mut exponent = 1i32;
-            for _val in 0..(exponent_kind as u8) {
-                exponent *= 10;
-            }
+    Display::fmt(&PriceValue::new(price, exponent_kind), f)
+}
+
+/// The number of billionths in a single unit of price, which every exponent divides exactly.
+const NANOS_PER_UNIT: i64 = 1_000_000_000;
+
+/// A price paired with the exponent (price type) needed to interpret it
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PriceValue {
+    price: Price,
+    exponent: ExponentKind,
+}
+
+impl PriceValue {
+    /// Pair a price with its exponent.
+    pub fn new(price: Price, exponent: ExponentKind) -> Self {
+        Self { price, exponent }
+    }
+
+    /// Retrieve the raw price.
+    pub fn price(&self) -> Price {
+        self.price
+    }
+
+    /// Retrieve the exponent of the price.
+    pub fn exponent(&self) -> ExponentKind {
+        self.exponent
+    }
 
-            let mantissa = value / exponent;
-            let decimal = value % exponent;
+    /// Retrieve the type of value contained in the price.
+    pub fn kind(&self) -> PriceKind {
+        self.price.kind()
+    }
+
+    /// Convert the price to another exponent, using XCAPI's rounding rules.
+    pub fn convert(&self, to: ExponentKind) -> XResult<Self> {
+        Ok(Self::new(self.price.convert(self.exponent, to)?, to))
+    }
 
-            tracing::trace!("{value} to {exponent_kind} and {exponent} => {mantissa}.{decimal}");
+    /// Convert a floating point value to a price, with an exponent chosen by XCAPI.
+    pub fn from_f64(value: f64) -> XResult<Self> {
+        let mut price = 0;
+        let mut exponent = 0;
+        Success::try_from(unsafe {
+            rxegy_sys::xcCvtDoubleToPrice(value, &mut price, &mut exponent)
+        })?;
+        Ok(Self::new(Price(price), ExponentKind::try_from(exponent)?))
+    }
+
+    /// Convert the price to a floating point value, if it is a normal price.
+    pub fn to_f64(&self) -> Option<f64> {
+        self.price
+            .value()
+            .map(|value| f64::from(value) / self.exponent.denominator() as f64)
+    }
 
+    /// Convert the price to an exact decimal value, if it is a normal price.
+    #[cfg(feature = "decimal")]
+    pub fn to_decimal(&self) -> Option<Decimal> {
+        let value = self.price.value()?;
+        match self.exponent.decimal_places() {
+            Some(places) => Some(Decimal::new(i64::from(value), places)),
+            // 512ths have an exact nine digit decimal expansion
+            None => Some(Decimal::new(self.to_nanos()?, 9)),
+        }
+    }
+
+    /// Add two prices, which may have different exponents.
+    ///
+    /// The result uses the finer of the two exponents, and is `None` if either price is not a
+    /// normal price, or the sum cannot be represented.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let nanos = self.to_nanos()?.checked_add(other.to_nanos()?)?;
+        Self::from_nanos(nanos, self.exponent.finest(other.exponent))
+    }
+
+    /// Subtract a price from this one, which may have a different exponent.
+    ///
+    /// The result uses the finer of the two exponents, and is `None` if either price is not a
+    /// normal price, or the difference cannot be represented.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        let nanos = self.to_nanos()?.checked_sub(other.to_nanos()?)?;
+        Self::from_nanos(nanos, self.exponent.finest(other.exponent))
+    }
+
+    /// Compare the values of two prices, which may have different exponents.
+    ///
+    /// This is `None` if either price is not a normal price.
+    pub fn checked_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.to_nanos()?.cmp(&other.to_nanos()?))
+    }
+
+    /// Round the price to the nearest multiple of the given tick size, rounding halves away from
+    /// zero.
+    ///
+    /// The result uses the finer of the two exponents, and is `None` if either price is not a
+    /// normal price, the tick size is not positive, or the result cannot be represented.
+    pub fn round_to_tick(&self, tick: &Self) -> Option<Self> {
+        let value = self.to_nanos()?;
+        let tick_nanos = tick.to_nanos()?;
+        if tick_nanos <= 0 {
+            return None;
+        }
+
+        let mut ticks = value / tick_nanos;
+        let remainder = (value % tick_nanos).abs();
+        if remainder * 2 >= tick_nanos {
+            ticks += value.signum();
+        }
+
+        Self::from_nanos(
+            ticks.checked_mul(tick_nanos)?,
+            self.exponent.finest(tick.exponent),
+        )
+    }
+
+    /// The value of a normal price, in billionths.
+    fn to_nanos(self) -> Option<i64> {
+        let value = i64::from(self.price.value()?);
+        Some(value * (NANOS_PER_UNIT / self.exponent.denominator()))
+    }
+
+    /// Express a value in billionths with the given exponent, if it is exactly representable.
+    fn from_nanos(nanos: i64, exponent: ExponentKind) -> Option<Self> {
+        let per_unit = NANOS_PER_UNIT / exponent.denominator();
+        if nanos % per_unit != 0 {
+            return None;
+        }
+
+        // Values which collide with a sentinel are no more representable than ones out of range
+        let price = Price::from(i32::try_from(nanos / per_unit).ok()?);
+        price.value()?;
+
+        Some(Self::new(price, exponent))
+    }
+}
+
+impl Display for PriceValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.price.kind() {
+            PriceKind::Blank => Ok(()),
+            PriceKind::Normal(value) => write_scaled(f, i64::from(value), self.exponent),
+            PriceKind::Market => write!(f, "*"),
+            PriceKind::Overflow => write!(f, "(OVER)"),
+            PriceKind::Underflow => write!(f, "(UNDER)"),
+        }
+    }
+}
+
+/// Write a mantissa with the given exponent as an exact decimal string.
+fn write_scaled(f: &mut Formatter<'_>, value: i64, exponent: ExponentKind) -> FmtResult {
+    let sign = if value < 0 { "-" } else { "" };
+    let value = u128::from(value.unsigned_abs());
+
+    match exponent.decimal_places() {
+        Some(0) => write!(f, "{sign}{value}"),
+        Some(places) => {
+            let denominator = exponent.denominator() as u128;
             write!(
                 f,
-                "{mantissa}.{decimal:0exponent$}",
-                exponent = exponent_kind as usize
+                "{sign}{}.{:0places$}",
+                value / denominator,
+                value % denominator,
+                places = places as usize
             )
         }
-        PriceKind::Market => write!(f, "*"),
-        PriceKind::Overflow => write!(f, "(OVER)"),
-        PriceKind::Underflow => write!(f, "(UNDER)"),
+        None => {
+            let nanos = value * (NANOS_PER_UNIT / exponent.denominator()) as u128;
+            let unit = NANOS_PER_UNIT as u128;
+            write!(f, "{sign}{}.{:09}", nanos / unit, nanos % unit)
+        }
     }
 }
 
+/// A price which carries its own exponent, as used by some XCAPI fields
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct PriceDesc(XC_PRICE_DESC);
+
+impl PriceDesc {
+    /// Create a price descriptor from a price and its exponent.
+    pub fn new(value: PriceValue) -> Self {
+        let mut desc = XC_PRICE_DESC {
+            xpd_price: value.price.0,
+            ..Default::default()
+        };
+        desc.set_xpd_price_type(value.exponent as u8);
+        Self(desc)
+    }
+
+    /// Retrieve the price and its exponent.
+    pub fn value(&self) -> XResult<PriceValue> {
+        let exponent = ExponentKind::try_from(self.0.xpd_price_type())?;
+        Ok(PriceValue::new(Price(self.0.xpd_price), exponent))
+    }
+
+    /// Convert the price to another exponent, using XCAPI's rounding rules.
+    pub fn convert(&self, to: ExponentKind) -> XResult<Self> {
+        let mut desc = self.0;
+        Success::try_from(unsafe { rxegy_sys::xcCvtPriceDesc(&mut desc, to as u8) })?;
+        Ok(Self(desc))
+    }
+
+    /// Convert a floating point value to a price descriptor, with an exponent chosen by XCAPI.
+    pub fn from_f64(value: f64) -> XResult<Self> {
+        let mut desc = XC_PRICE_DESC::default();
+        Success::try_from(unsafe { rxegy_sys::xcCvtDoubleToPriceDesc(value, &mut desc) })?;
+        Ok(Self(desc))
+    }
+
+    /// Convert the price to a floating point value.
+    pub fn to_f64(&self) -> XResult<f64> {
+        let mut out = 0.0;
+        Success::try_from(unsafe { rxegy_sys::xcCvtPriceDescToDouble(&self.0, &mut out) })?;
+        Ok(out)
+    }
+}
+
+impl From<PriceValue> for PriceDesc {
+    fn from(value: PriceValue) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<XC_PRICE_DESC> for PriceDesc {
+    fn from(value: XC_PRICE_DESC) -> Self {
+        Self(value)
+    }
+}
+
+unsafe impl FieldValue for PriceDesc {}
+
+/// An accumulated traded value (the sum of price times size), paired with its exponent
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TradedValue {
+    value: i64,
+    exponent: ExponentKind,
+}
+
+impl TradedValue {
+    /// Pair a raw traded value with its exponent.
+    pub fn new(value: i64, exponent: ExponentKind) -> Self {
+        Self { value, exponent }
+    }
+
+    /// Retrieve the raw traded value.
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    /// Retrieve the exponent of the traded value.
+    pub fn exponent(&self) -> ExponentKind {
+        self.exponent
+    }
+
+    /// Convert the traded value to another exponent, using XCAPI's rounding rules.
+    pub fn convert(&self, to: ExponentKind) -> XResult<Self> {
+        let mut out = 0;
+        Success::try_from(unsafe {
+            rxegy_sys::xcCvtTradedValue(&self.value, self.exponent as u8, to as u8, &mut out)
+        })?;
+        Ok(Self::new(out, to))
+    }
+
+    /// Convert a floating point value to a traded value, with an exponent chosen by XCAPI.
+    pub fn from_f64(value: f64) -> XResult<Self> {
+        let mut out = 0;
+        let mut exponent = 0;
+        Success::try_from(unsafe {
+            rxegy_sys::xcCvtDoubleToTradedValue(value, &mut out, &mut exponent)
+        })?;
+        Ok(Self::new(out, ExponentKind::try_from(exponent)?))
+    }
+
+    /// Convert the traded value to a floating point value.
+    pub fn to_f64(&self) -> XResult<f64> {
+        let mut out = 0.0;
+        Success::try_from(unsafe {
+            rxegy_sys::xcCvtTradedValueToDouble(&self.value, self.exponent as u8, &mut out)
+        })?;
+        Ok(out)
+    }
+
+    /// Convert the traded value to an exact decimal value.
+    #[cfg(feature = "decimal")]
+    pub fn to_decimal(&self) -> Decimal {
+        match self.exponent.decimal_places() {
+            Some(places) => Decimal::new(self.value, places),
+            // 512ths have an exact nine digit decimal expansion
+            None => Decimal::from_i128_with_scale(
+                i128::from(self.value) * i128::from(NANOS_PER_UNIT / self.exponent.denominator()),
+                9,
+            ),
+        }
+    }
+}
+
+impl Display for TradedValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write_scaled(f, self.value, self.exponent)
+    }
+}
+
+/// A decimal floating point value, with a 60-bit mantissa and a 4-bit count of decimal places
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct DFloat(XC_DFLOAT_64);
+
+impl DFloat {
+    /// The largest number of decimal places a dfloat can carry.
+    pub const MAX_EXPONENT: u8 = 15;
+
+    /// Create a dfloat from a mantissa and a number of decimal places, if the mantissa fits in 60
+    /// bits.
+    pub fn new(mantissa: i64, exponent: u8) -> Option<Self> {
+        if exponent > Self::MAX_EXPONENT || (mantissa << 4) >> 4 != mantissa {
+            return None;
+        }
+
+        Some(Self(XC_DFLOAT_64 {
+            s64: (mantissa << 4) | i64::from(exponent),
+        }))
+    }
+
+    /// Retrieve the raw 64-bit representation.
+    pub fn raw(&self) -> i64 {
+        // SAFETY: both variants of the union are the same plain 64-bit integer
+        unsafe { self.0.s64 }
+    }
+
+    /// Retrieve the signed mantissa.
+    pub fn mantissa(&self) -> i64 {
+        self.raw() >> 4
+    }
+
+    /// Retrieve the number of decimal places.
+    pub fn exponent(&self) -> u8 {
+        (self.raw() & 0xf) as u8
+    }
+
+    /// Rescale the dfloat to the given number of decimal places.
+    pub fn rescale(&self, exponent: u8) -> XResult<Self> {
+        let mut out = self.0;
+        Success::try_from(unsafe { rxegy_sys::xcCvtDfloat_64(&mut out, u64::from(exponent)) })?;
+        Ok(Self(out))
+    }
+
+    /// Convert a floating point value to a dfloat.
+    pub fn from_f64(value: f64) -> XResult<Self> {
+        let mut out = XC_DFLOAT_64 { s64: 0 };
+        Success::try_from(unsafe { rxegy_sys::xcCvtDoubleToDfloat_64(value, &mut out) })?;
+        Ok(Self(out))
+    }
+
+    /// Convert the dfloat to a floating point value.
+    pub fn to_f64(&self) -> XResult<f64> {
+        let mut out = 0.0;
+        Success::try_from(unsafe { rxegy_sys::xcCvtDfloat_64ToDouble(&self.0, &mut out) })?;
+        Ok(out)
+    }
+
+    /// Convert a price to a dfloat.
+    pub fn from_price(value: PriceValue) -> XResult<Self> {
+        let mut out = XC_DFLOAT_64 { s64: 0 };
+        Success::try_from(unsafe {
+            rxegy_sys::xcCvtXcPriceToDfloat_64(&value.price.0, value.exponent as u8, &mut out)
+        })?;
+        Ok(Self(out))
+    }
+
+    /// Convert the dfloat to a price, with an exponent chosen by XCAPI.
+    pub fn to_price(&self) -> XResult<PriceValue> {
+        let mut price = 0;
+        let mut exponent = 0;
+        Success::try_from(unsafe {
+            rxegy_sys::xcCvtDfloat_64ToXcPrice(&self.0, &mut price, &mut exponent)
+        })?;
+        Ok(PriceValue::new(
+            Price(price),
+            ExponentKind::try_from(exponent)?,
+        ))
+    }
+
+    /// Convert a volume to a dfloat.
+    ///
+    /// XCAPI volumes are 32 bits wide, so larger volumes cannot be converted.
+    pub fn from_volume(value: Volume) -> XResult<Self> {
+        let volume = u32::try_from(u64::from(value)).map_err(|_| ExegyError::Overflow)?;
+        let mut out = XC_DFLOAT_64 { s64: 0 };
+        Success::try_from(unsafe { rxegy_sys::xcCvtXcVolumeToDfloat_64(&volume, &mut out) })?;
+        Ok(Self(out))
+    }
+
+    /// Convert the dfloat to a volume.
+    pub fn to_volume(&self) -> XResult<Volume> {
+        let mut out = 0;
+        Success::try_from(unsafe { rxegy_sys::xcCvtDfloat_64ToXcVolume(&self.0, &mut out) })?;
+        Ok(Volume::from(u64::from(out)))
+    }
+
+    /// Convert the dfloat to an exact decimal value.
+    #[cfg(feature = "decimal")]
+    pub fn to_decimal(&self) -> Decimal {
+        Decimal::new(self.mantissa(), u32::from(self.exponent()))
+    }
+}
+
+impl Debug for DFloat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("DFloat")
+            .field("mantissa", &self.mantissa())
+            .field("exponent", &self.exponent())
+            .finish()
+    }
+}
+
+impl Display for DFloat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mantissa = self.mantissa();
+        let places = usize::from(self.exponent());
+        if places == 0 {
+            return write!(f, "{mantissa}");
+        }
+
+        let sign = if mantissa < 0 { "-" } else { "" };
+        let value = mantissa.unsigned_abs();
+        let denominator = 10u64.pow(places as u32);
+        write!(
+            f,
+            "{sign}{}.{:0places$}",
+            value / denominator,
+            value % denominator
+        )
+    }
+}
+
+impl PartialEq for DFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.raw() == other.raw()
+    }
+}
+
+impl Eq for DFloat {}
+
+impl Hash for DFloat {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.raw().hash(state);
+    }
+}
+
+impl From<XC_DFLOAT_64> for DFloat {
+    fn from(value: XC_DFLOAT_64) -> Self {
+        Self(value)
+    }
+}
+
+unsafe impl FieldValue for DFloat {}
+
 /// An enumeration of Exegy price (exponent) types
 #[derive(Clone, Copy, Debug, displaydoc::Display, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(u8)]
@@ -144,3 +598,300 @@ impl TryFrom<u8> for ExponentKind {
         }
     }
 }
+
+impl ExponentKind {
+    /// The number of decimal places, or `None` for fractional prices.
+    fn decimal_places(self) -> Option<u32> {
+        match self {
+            Self::Fractional512 => None,
+            decimal => Some(decimal as u32),
+        }
+    }
+
+    /// The number of mantissa units in a single unit of price.
+    fn denominator(self) -> i64 {
+        match self.decimal_places() {
+            Some(places) => 10i64.pow(places),
+            None => 512,
+        }
+    }
+
+    /// The exponent which can exactly represent any value of either exponent.
+    fn finest(self, other: Self) -> Self {
+        match (self.decimal_places(), other.decimal_places()) {
+            (Some(ours), Some(theirs)) if ours >= theirs => self,
+            (Some(_), Some(_)) => other,
+            (None, None) => self,
+            // A decimal and a fraction only share billionths
+            _ => Self::Decimal9,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXPONENTS: [ExponentKind; 11] = [
+        ExponentKind::Decimal0,
+        ExponentKind::Decimal1,
+        ExponentKind::Decimal2,
+        ExponentKind::Decimal3,
+        ExponentKind::Decimal4,
+        ExponentKind::Decimal5,
+        ExponentKind::Decimal6,
+        ExponentKind::Decimal7,
+        ExponentKind::Decimal8,
+        ExponentKind::Decimal9,
+        ExponentKind::Fractional512,
+    ];
+
+    const MANTISSAS: [i32; 7] = [0, 1, -1, 150, -150, 12_345, -98_765];
+
+    /// Every normal price, paired with each exponent it can be exactly converted to.
+    fn exact_conversions() -> impl Iterator<Item = (PriceValue, PriceValue)> {
+        EXPONENTS.into_iter().flat_map(|from| {
+            MANTISSAS.into_iter().flat_map(move |mantissa| {
+                let value = PriceValue::new(Price::from(mantissa), from);
+                EXPONENTS.into_iter().filter_map(move |to| {
+                    // Adding zero in the target exponent takes the pure-Rust path
+                    let zero = PriceValue::new(Price::from(0), to);
+                    value
+                        .checked_add(&zero)
+                        .filter(|expected| expected.exponent() == to)
+                        .map(|expected| (value, expected))
+                })
+            })
+        })
+    }
+
+    #[test]
+    fn convert_matches_pure_rust() {
+        for (value, expected) in exact_conversions() {
+            let converted = value.convert(expected.exponent()).expect("convert");
+            assert_eq!(converted, expected, "{value}");
+            assert_eq!(converted.checked_cmp(&value), Some(Ordering::Equal));
+        }
+    }
+
+    #[test]
+    fn price_desc_round_trip() {
+        for (value, expected) in exact_conversions() {
+            let desc = PriceDesc::new(value);
+            assert_eq!(desc.value().expect("value"), value);
+
+            let converted = desc.convert(expected.exponent()).expect("convert");
+            assert_eq!(converted.value().expect("value"), expected);
+
+            let double = desc.to_f64().expect("to_f64");
+            assert!((double - value.to_f64().expect("normal")).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn traded_value_round_trip() {
+        for (value, expected) in exact_conversions() {
+            let traded = TradedValue::new(i64::from(value.price().raw_value()), value.exponent());
+            let converted = traded.convert(expected.exponent()).expect("convert");
+            assert_eq!(
+                converted.value(),
+                i64::from(expected.price().raw_value()),
+                "{traded}"
+            );
+            assert_eq!(
+                converted.convert(traded.exponent()).expect("convert"),
+                traded
+            );
+
+            #[cfg(feature = "decimal")]
+            assert_eq!(converted.to_decimal(), traded.to_decimal());
+        }
+    }
+
+    #[test]
+    fn dfloat_round_trip() {
+        for (value, _) in exact_conversions() {
+            let dfloat = DFloat::from_price(value).expect("from_price");
+            let price = dfloat.to_price().expect("to_price");
+            assert_eq!(price.checked_cmp(&value), Some(Ordering::Equal), "{dfloat}");
+
+            #[cfg(feature = "decimal")]
+            assert_eq!(dfloat.to_decimal(), value.to_decimal().expect("normal"));
+        }
+    }
+
+    #[test]
+    fn dfloat_parts() {
+        let dfloat = DFloat::new(-12_345, 3).expect("fits");
+        assert_eq!(dfloat.mantissa(), -12_345);
+        assert_eq!(dfloat.exponent(), 3);
+        assert_eq!(dfloat.to_string(), "-12.345");
+        assert!(DFloat::new(i64::MAX, 0).is_none());
+        assert!(DFloat::new(1, DFloat::MAX_EXPONENT + 1).is_none());
+    }
+
+    const SENTINELS: [(i32, PriceKind, &str); 5] = [
+        (rxegy_sys::XC_BLANK_PRICE, PriceKind::Blank, ""),
+        (rxegy_sys::XC_BLANK_PRICE_HIGH, PriceKind::Blank, ""),
+        (rxegy_sys::XC_MARKET_PRICE, PriceKind::Market, "*"),
+        (rxegy_sys::XC_OVERFLOW_PRICE, PriceKind::Overflow, "(OVER)"),
+        (
+            rxegy_sys::XC_UNDERFLOW_PRICE,
+            PriceKind::Underflow,
+            "(UNDER)",
+        ),
+    ];
+
+    fn price(mantissa: i32, exponent: ExponentKind) -> PriceValue {
+        PriceValue::new(Price::from(mantissa), exponent)
+    }
+
+    #[test]
+    fn sentinels_are_not_values() {
+        let one = price(1, ExponentKind::Decimal2);
+
+        for (raw, kind, display) in SENTINELS {
+            let sentinel = price(raw, ExponentKind::Decimal2);
+            assert_eq!(sentinel.price().value(), None, "{raw}");
+            assert_eq!(sentinel.kind(), kind, "{raw}");
+            assert_eq!(sentinel.to_string(), display, "{raw}");
+            assert_eq!(sentinel.to_f64(), None, "{raw}");
+            assert_eq!(sentinel.checked_cmp(&one), None, "{raw}");
+            assert_eq!(one.checked_cmp(&sentinel), None, "{raw}");
+            assert_eq!(sentinel.checked_add(&one), None, "{raw}");
+            assert_eq!(one.checked_sub(&sentinel), None, "{raw}");
+            assert_eq!(sentinel.round_to_tick(&one), None, "{raw}");
+            assert_eq!(one.round_to_tick(&sentinel), None, "{raw}");
+
+            #[cfg(feature = "decimal")]
+            assert_eq!(sentinel.to_decimal(), None, "{raw}");
+        }
+    }
+
+    #[test]
+    fn arithmetic_never_produces_sentinels() {
+        let one = price(1, ExponentKind::Decimal0);
+
+        let highest = price(rxegy_sys::XC_OVERFLOW_PRICE - 1, ExponentKind::Decimal0);
+        assert_eq!(
+            highest.kind(),
+            PriceKind::Normal(rxegy_sys::XC_OVERFLOW_PRICE - 1)
+        );
+        assert_eq!(
+            highest.checked_sub(&one).map(|v| v.kind()),
+            Some(PriceKind::Normal(rxegy_sys::XC_OVERFLOW_PRICE - 2))
+        );
+        assert_eq!(highest.checked_add(&one), None);
+        assert_eq!(highest.checked_add(&highest), None);
+
+        let lowest = price(rxegy_sys::XC_BLANK_PRICE + 1, ExponentKind::Decimal0);
+        assert_eq!(
+            lowest.kind(),
+            PriceKind::Normal(rxegy_sys::XC_BLANK_PRICE + 1)
+        );
+        assert_eq!(lowest.checked_sub(&one), None);
+        assert_eq!(lowest.checked_add(&lowest), None);
+
+        // One above the lowest normal price is the underflow sentinel
+        assert_eq!(lowest.checked_add(&one), None);
+        let two = price(2, ExponentKind::Decimal0);
+        assert_eq!(
+            lowest.checked_add(&two).map(|v| v.kind()),
+            Some(PriceKind::Normal(rxegy_sys::XC_UNDERFLOW_PRICE + 1))
+        );
+
+        // Rescaling to a finer exponent overflows the mantissa long before the nanos do
+        let fine = price(0, ExponentKind::Decimal9);
+        assert_eq!(price(3, ExponentKind::Decimal0).checked_add(&fine), None);
+        assert_eq!(
+            price(2, ExponentKind::Decimal0).checked_add(&fine),
+            Some(price(2_000_000_000, ExponentKind::Decimal9))
+        );
+    }
+
+    #[test]
+    fn mixed_exponents() {
+        let half = price(5, ExponentKind::Decimal1);
+        let fraction = price(256, ExponentKind::Fractional512);
+        assert_eq!(half.checked_cmp(&fraction), Some(Ordering::Equal));
+
+        let sum = half
+            .checked_add(&price(1, ExponentKind::Fractional512))
+            .expect("sum");
+        assert_eq!(sum, price(501_953_125, ExponentKind::Decimal9));
+        assert_eq!(sum.to_string(), "0.501953125");
+
+        let difference = price(-150, ExponentKind::Decimal2)
+            .checked_sub(&price(25, ExponentKind::Decimal3))
+            .expect("difference");
+        assert_eq!(difference, price(-1_525, ExponentKind::Decimal3));
+        assert_eq!(difference.to_string(), "-1.525");
+        assert_eq!(
+            difference.checked_cmp(&price(-1, ExponentKind::Decimal0)),
+            Some(Ordering::Less)
+        );
+    }
+
+    #[test]
+    fn round_to_tick() {
+        let nickel = price(5, ExponentKind::Decimal2);
+        let cases = [
+            (1_234, 1_250),
+            (1_224, 1_200),
+            (1_225, 1_250),
+            (1_250, 1_250),
+            (0, 0),
+            (-1_224, -1_200),
+            (-1_225, -1_250),
+            (-1_234, -1_250),
+        ];
+
+        for (mantissa, expected) in cases {
+            let rounded = price(mantissa, ExponentKind::Decimal3)
+                .round_to_tick(&nickel)
+                .expect("rounded");
+            assert_eq!(
+                rounded,
+                price(expected, ExponentKind::Decimal3),
+                "{mantissa}"
+            );
+        }
+
+        // A fractional tick on a decimal price rounds in billionths
+        let eighth = price(64, ExponentKind::Fractional512);
+        assert_eq!(
+            price(10, ExponentKind::Decimal2).round_to_tick(&eighth),
+            Some(price(125_000_000, ExponentKind::Decimal9))
+        );
+
+        // The tick must be positive
+        assert_eq!(
+            nickel.round_to_tick(&price(0, ExponentKind::Decimal2)),
+            None
+        );
+        assert_eq!(
+            nickel.round_to_tick(&price(-5, ExponentKind::Decimal2)),
+            None
+        );
+
+        // Rounding up past the highest normal price is not representable
+        let highest = price(rxegy_sys::XC_OVERFLOW_PRICE - 1, ExponentKind::Decimal0);
+        assert_eq!(
+            highest.round_to_tick(&price(8, ExponentKind::Decimal0)),
+            None
+        );
+        assert_eq!(
+            highest.round_to_tick(&price(1, ExponentKind::Decimal0)),
+            Some(highest)
+        );
+    }
+
+    #[test]
+    fn display_negative() {
+        let value = PriceValue::new(Price::from(-50), ExponentKind::Decimal2);
+        assert_eq!(value.to_string(), "-0.50");
+
+        let value = PriceValue::new(Price::from(-513), ExponentKind::Fractional512);
+        assert_eq!(value.to_string(), "-1.001953125");
+    }
+}
@@ -0,0 +1,524 @@
+//! Event Qualifiers and Flags
+
+use rxegy_sys::{
+    XC_EQUITY_TRADE_UPDATE_FLAGS, XC_QUOTE_QUALIFIERS, XC_QUOTE_QUALS, XC_REFRESH_QUALIFIERS,
+    XC_REFRESH_QUALS, XC_TICK_FLAGS, XC_TRADE_QUALIFIERS, XC_TRADE_QUALS,
+};
+
+/// Implement a single qualifier code type, and the set of qualifiers carried by an event.
+macro_rules! impl_qualifiers {
+    (
+        $(#[$qual_meta:meta])*
+        $qual:ident, $prefix:literal,
+        $(#[$quals_meta:meta])*
+        $quals:ident, $packed:ty, $arr:ident, $wide:ty
+    ) => {
+        $(#[$qual_meta])*
+        #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #[repr(transparent)]
+        pub struct $qual(u16);
+
+        impl $qual {
+            const fn new(code: u32) -> Self {
+                Self(code as u16)
+            }
+
+            #[doc = concat!("Get the raw `", $prefix, "` code of this qualifier.")]
+            pub fn code(&self) -> u16 {
+                self.0
+            }
+        }
+
+        impl From<u16> for $qual {
+            fn from(value: u16) -> Self {
+                Self(value)
+            }
+        }
+
+        $(#[$quals_meta])*
+        #[doc = ""]
+        #[doc = concat!("Unused entries hold [`", stringify!($qual), "::REGULAR`].")]
+        #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+        pub struct $quals([$qual; 8]);
+
+        impl $quals {
+            #[doc = concat!("Iterate over every qualifier other than [`", stringify!($qual), "::REGULAR`].")]
+            pub fn iter(&self) -> impl Iterator<Item = $qual> + '_ {
+                self.0
+                    .iter()
+                    .copied()
+                    .filter(|qual| *qual != $qual::REGULAR)
+            }
+
+            /// Whether any entry is the given qualifier.
+            pub fn contains(&self, qual: $qual) -> bool {
+                self.0.contains(&qual)
+            }
+
+            /// Whether any entry is one of the given qualifiers.
+            pub fn contains_any(&self, quals: &[$qual]) -> bool {
+                self.0.iter().any(|qual| quals.contains(qual))
+            }
+
+            /// Whether there are no qualifiers other than the regular one.
+            pub fn is_regular(&self) -> bool {
+                self.iter().next().is_none()
+            }
+        }
+
+        impl From<$packed> for $quals {
+            fn from(value: $packed) -> Self {
+                // SAFETY: every variant of the union is plain integer data of the same size
+                let arr = unsafe { value.$arr };
+                let mut quals = [$qual::REGULAR; 8];
+
+                for (qual, code) in quals.iter_mut().zip(arr) {
+                    *qual = $qual(u16::from(code));
+                }
+
+                Self(quals)
+            }
+        }
+
+        impl From<$wide> for $quals {
+            fn from(value: $wide) -> Self {
+                Self({ value.entries }.map($qual))
+            }
+        }
+    };
+}
+
+impl_qualifiers! {
+    /// A single normalized trade qualifier (sale condition)
+    TradeQualifier, "XTQ_",
+    /// The normalized trade qualifiers (sale conditions) of a trade
+    TradeQualifiers, XC_TRADE_QUALS, xtq_arr, XC_TRADE_QUALIFIERS
+}
+
+impl_qualifiers! {
+    /// A single normalized quote qualifier (quote condition)
+    QuoteQualifier, "XQQ_",
+    /// The normalized quote qualifiers (quote conditions) of a quote
+    QuoteQualifiers, XC_QUOTE_QUALS, xqq_arr, XC_QUOTE_QUALIFIERS
+}
+
+impl_qualifiers! {
+    /// A single normalized refresh qualifier
+    RefreshQualifier, "XRQ_",
+    /// The normalized qualifiers of a refresh, indicating why it was published
+    RefreshQualifiers, XC_REFRESH_QUALS, xrq_arr, XC_REFRESH_QUALIFIERS
+}
+
+impl TradeQualifier {
+    /// A regular trade, with no special conditions.
+    pub const REGULAR: Self = Self::new(rxegy_sys::XTQ_REG);
+    /// The trade was reported out of sequence.
+    pub const OUT_OF_SEQUENCE: Self = Self::new(rxegy_sys::XTQ_OSEQ);
+    /// The trade is cancelled.
+    pub const CANCEL: Self = Self::new(rxegy_sys::XTQ_CANC);
+    /// The last reported trade is cancelled.
+    pub const LAST_CANCEL: Self = Self::new(rxegy_sys::XTQ_LAST_CANC);
+    /// The trade was reported late.
+    pub const LATE: Self = Self::new(rxegy_sys::XTQ_LATE);
+    /// The opening trade is cancelled.
+    pub const CANCEL_OPEN: Self = Self::new(rxegy_sys::XTQ_CANC_OPN);
+    /// The opening trade.
+    pub const OPEN: Self = Self::new(rxegy_sys::XTQ_OPEN);
+    /// The trade was automatically executed.
+    pub const AUTO_EXECUTION: Self = Self::new(rxegy_sys::XTQ_AUTO);
+    /// The reopening trade, following a halt.
+    pub const REOPEN: Self = Self::new(rxegy_sys::XTQ_REOP);
+    /// The trade is settled in cash.
+    pub const CASH: Self = Self::new(rxegy_sys::XTQ_CASH);
+    /// The trade was executed at an average price.
+    pub const AVERAGE_PRICE: Self = Self::new(rxegy_sys::XTQ_AVG);
+    /// The trade was executed as part of an intermarket sweep.
+    pub const INTERMARKET_SWEEP: Self = Self::new(rxegy_sys::XTQ_SWEEP);
+    /// The trade settles on the next day.
+    pub const NEXT_DAY: Self = Self::new(rxegy_sys::XTQ_NDT);
+    /// The trade settles on a date chosen by the seller.
+    pub const SELLER: Self = Self::new(rxegy_sys::XTQ_SELLER);
+    /// The trade occurred outside of regular trading hours.
+    pub const EXTENDED_HOURS: Self = Self::new(rxegy_sys::XTQ_XHOURS);
+    /// The trade was derivatively priced.
+    pub const DERIVATIVELY_PRICED: Self = Self::new(rxegy_sys::XTQ_DERIV);
+    /// The trade references a prior price.
+    pub const PRIOR_REFERENCE_PRICE: Self = Self::new(rxegy_sys::XTQ_PRIOR_REF);
+    /// The opening prints of the market.
+    pub const OPENING_PRINTS: Self = Self::new(rxegy_sys::XTQ_OPENING_PRINTS);
+    /// The reopening prints of the market, following a halt.
+    pub const REOPENING_PRINTS: Self = Self::new(rxegy_sys::XTQ_REOPEN_PRINTS);
+    /// The closing prints of the market.
+    pub const CLOSING_PRINTS: Self = Self::new(rxegy_sys::XTQ_CLOSING_PRINTS);
+    /// The trade was for less than a board (round) lot.
+    pub const NON_BOARD_LOT: Self = Self::new(rxegy_sys::XTQ_NBL);
+    /// The trade was reported after the last trade, but out of sequence.
+    pub const SOLD_LAST: Self = Self::new(rxegy_sys::XTQ_SOLDLAST);
+    /// The trade occurred outside of regular trading hours, and was reported out of sequence.
+    pub const EXTENDED_HOURS_OUT_OF_SEQUENCE: Self = Self::new(rxegy_sys::XTQ_XHOURS_OSEQ);
+    /// The trade was reported out of sequence by the local market.
+    pub const LOCAL_OUT_OF_SEQUENCE: Self = Self::new(rxegy_sys::XTQ_LOCAL_OSEQ);
+    /// The trade is exempt from trade-through rules.
+    pub const TRADE_THROUGH_EXEMPT: Self = Self::new(rxegy_sys::XTQ_TTE);
+    /// The trade was reported as of an earlier time.
+    pub const AS_OF: Self = Self::new(rxegy_sys::XTQ_AS_OF_TRADE);
+    /// The qualifier is not valid.
+    pub const INVALID: Self = Self::new(rxegy_sys::XTQ_INVAL);
+}
+
+impl TradeQualifiers {
+    /// Whether the trade was for less than a board (round) lot.
+    pub fn is_odd_lot(&self) -> bool {
+        self.contains(TradeQualifier::NON_BOARD_LOT)
+    }
+
+    /// Whether the trade was reported late.
+    pub fn is_late(&self) -> bool {
+        self.contains_any(&[TradeQualifier::LATE, TradeQualifier::AS_OF])
+    }
+
+    /// Whether the trade was reported out of sequence.
+    pub fn is_out_of_sequence(&self) -> bool {
+        self.contains_any(&[
+            TradeQualifier::OUT_OF_SEQUENCE,
+            TradeQualifier::EXTENDED_HOURS_OUT_OF_SEQUENCE,
+            TradeQualifier::LOCAL_OUT_OF_SEQUENCE,
+            TradeQualifier::SOLD_LAST,
+        ])
+    }
+
+    /// Whether the trade is an opening print.
+    pub fn is_opening_print(&self) -> bool {
+        self.contains_any(&[TradeQualifier::OPEN, TradeQualifier::OPENING_PRINTS])
+    }
+
+    /// Whether the trade is a reopening print, following a halt.
+    pub fn is_reopening_print(&self) -> bool {
+        self.contains_any(&[TradeQualifier::REOPEN, TradeQualifier::REOPENING_PRINTS])
+    }
+
+    /// Whether the trade is a closing print.
+    pub fn is_closing_print(&self) -> bool {
+        self.contains(TradeQualifier::CLOSING_PRINTS)
+    }
+
+    /// Whether the trade occurred outside of regular trading hours.
+    pub fn is_extended_hours(&self) -> bool {
+        self.contains_any(&[
+            TradeQualifier::EXTENDED_HOURS,
+            TradeQualifier::EXTENDED_HOURS_OUT_OF_SEQUENCE,
+        ])
+    }
+
+    /// Whether the trade cancels an earlier trade.
+    pub fn is_cancel(&self) -> bool {
+        self.contains_any(&[
+            TradeQualifier::CANCEL,
+            TradeQualifier::LAST_CANCEL,
+            TradeQualifier::CANCEL_OPEN,
+        ])
+    }
+}
+
+impl QuoteQualifier {
+    /// A regular quote, with no special conditions.
+    pub const REGULAR: Self = Self::new(rxegy_sys::XQQ_REG);
+    /// The quote resumes trading following a halt.
+    pub const RESUME: Self = Self::new(rxegy_sys::XQQ_RESUME);
+    /// The closing quote.
+    pub const CLOSE: Self = Self::new(rxegy_sys::XQQ_CLOSE);
+    /// The opening quote.
+    pub const OPEN: Self = Self::new(rxegy_sys::XQQ_OPEN);
+    /// The quote was published outside of regular trading hours.
+    pub const EXTENDED_HOURS: Self = Self::new(rxegy_sys::XQQ_XHOURS);
+    /// The quote is firm.
+    pub const FIRM: Self = Self::new(rxegy_sys::XQQ_FIRM);
+    /// The quote is not firm.
+    pub const NON_FIRM: Self = Self::new(rxegy_sys::XQQ_NONFIRM);
+    /// The instrument is halted.
+    pub const HALTED: Self = Self::new(rxegy_sys::XQQ_HALTED);
+    /// The quote is slow on both sides.
+    pub const SLOW: Self = Self::new(rxegy_sys::XQQ_SLOW);
+    /// The bid is slow.
+    pub const SLOW_BID: Self = Self::new(rxegy_sys::XQQ_SLOW_B);
+    /// The offer is slow.
+    pub const SLOW_ASK: Self = Self::new(rxegy_sys::XQQ_SLOW_O);
+    /// The bid is slow because of a liquidity replenishment point (LRP) or limit up-limit down band.
+    pub const SLOW_LRP_BID: Self = Self::new(rxegy_sys::XQQ_SLOW_LRP_B);
+    /// The offer is slow because of a liquidity replenishment point (LRP) or limit up-limit down
+    /// band.
+    pub const SLOW_LRP_ASK: Self = Self::new(rxegy_sys::XQQ_SLOW_LRP_O);
+    /// The quote is slow because of a lagging market.
+    pub const SLOW_LAG: Self = Self::new(rxegy_sys::XQQ_SLOW_LAG);
+    /// The quote is slow because of a set slow list.
+    pub const SLOW_SSL: Self = Self::new(rxegy_sys::XQQ_SLOW_SSL);
+    /// The quote is published during an auction.
+    pub const AUCTION: Self = Self::new(rxegy_sys::XQQ_AUCTION);
+    /// The quote starts an auction.
+    pub const AUCTION_START: Self = Self::new(rxegy_sys::XQQ_AUCTION_START);
+    /// The quote ends an auction.
+    pub const AUCTION_END: Self = Self::new(rxegy_sys::XQQ_AUCTION_END);
+    /// The quote updates an auction in progress.
+    pub const AUCTION_UPDATE: Self = Self::new(rxegy_sys::XQQ_AUCTION_UPDATE);
+    /// The quote is published during a scheduled auction.
+    pub const SCHEDULED_AUCTION: Self = Self::new(rxegy_sys::XQQ_SCHED_AUCTION);
+    /// The quote is indicative only.
+    pub const INDICATIVE: Self = Self::new(rxegy_sys::XQQ_INDICATIVE);
+    /// The quote only has one side.
+    pub const ONE_SIDED: Self = Self::new(rxegy_sys::XQQ_ONE_SIDED);
+    /// There is retail interest on the bid.
+    pub const RETAIL_INTEREST_BID: Self = Self::new(rxegy_sys::XQQ_RI_B);
+    /// There is retail interest on the offer.
+    pub const RETAIL_INTEREST_ASK: Self = Self::new(rxegy_sys::XQQ_RI_O);
+    /// There is retail interest on both sides.
+    pub const RETAIL_INTEREST: Self = Self::new(rxegy_sys::XQQ_RI);
+    /// The quote was published before the market opened.
+    pub const PRE_MARKET: Self = Self::new(rxegy_sys::XQQ_PRE_MARKET);
+    /// The quote is for less than a board (round) lot.
+    pub const NON_BOARD_LOT: Self = Self::new(rxegy_sys::XQQ_NBL);
+    /// The quote was reported out of sequence by the local market.
+    pub const LOCAL_OUT_OF_SEQUENCE: Self = Self::new(rxegy_sys::XQQ_LOCAL_OSEQ);
+    /// The instrument is under a short sale restriction.
+    pub const SHORT_RESTRICTED: Self = Self::new(rxegy_sys::XQQ_SHORT_RESTRICT);
+    /// The qualifier is not valid.
+    pub const INVALID: Self = Self::new(rxegy_sys::XQQ_INVAL);
+}
+
+impl QuoteQualifiers {
+    /// Whether either side of the quote is slow.
+    pub fn is_slow(&self) -> bool {
+        self.contains_any(&[
+            QuoteQualifier::SLOW,
+            QuoteQualifier::SLOW_BID,
+            QuoteQualifier::SLOW_ASK,
+            QuoteQualifier::SLOW_LRP_BID,
+            QuoteQualifier::SLOW_LRP_ASK,
+            QuoteQualifier::SLOW_LAG,
+            QuoteQualifier::SLOW_SSL,
+        ])
+    }
+
+    /// Whether the bid is slow.
+    pub fn is_slow_bid(&self) -> bool {
+        self.contains_any(&[
+            QuoteQualifier::SLOW,
+            QuoteQualifier::SLOW_BID,
+            QuoteQualifier::SLOW_LRP_BID,
+        ])
+    }
+
+    /// Whether the offer is slow.
+    pub fn is_slow_ask(&self) -> bool {
+        self.contains_any(&[
+            QuoteQualifier::SLOW,
+            QuoteQualifier::SLOW_ASK,
+            QuoteQualifier::SLOW_LRP_ASK,
+        ])
+    }
+
+    /// Whether either side is slow because of a liquidity replenishment point (LRP) or limit
+    /// up-limit down band.
+    pub fn is_lrp(&self) -> bool {
+        self.contains_any(&[QuoteQualifier::SLOW_LRP_BID, QuoteQualifier::SLOW_LRP_ASK])
+    }
+
+    /// Whether the quote is not firm.
+    pub fn is_non_firm(&self) -> bool {
+        self.contains(QuoteQualifier::NON_FIRM)
+    }
+
+    /// Whether there is retail interest on either side of the quote.
+    pub fn is_retail_interest(&self) -> bool {
+        self.contains_any(&[
+            QuoteQualifier::RETAIL_INTEREST,
+            QuoteQualifier::RETAIL_INTEREST_BID,
+            QuoteQualifier::RETAIL_INTEREST_ASK,
+        ])
+    }
+
+    /// Whether the quote is part of an auction.
+    pub fn is_auction(&self) -> bool {
+        self.contains_any(&[
+            QuoteQualifier::AUCTION,
+            QuoteQualifier::AUCTION_START,
+            QuoteQualifier::AUCTION_END,
+            QuoteQualifier::AUCTION_UPDATE,
+            QuoteQualifier::SCHEDULED_AUCTION,
+        ])
+    }
+
+    /// Whether the quote is indicative only.
+    pub fn is_indicative(&self) -> bool {
+        self.contains(QuoteQualifier::INDICATIVE)
+    }
+
+    /// Whether the quote was published outside of regular trading hours.
+    pub fn is_extended_hours(&self) -> bool {
+        self.contains_any(&[QuoteQualifier::EXTENDED_HOURS, QuoteQualifier::PRE_MARKET])
+    }
+}
+
+impl RefreshQualifier {
+    /// A regular refresh.
+    pub const REGULAR: Self = Self::new(rxegy_sys::XRQ_REG);
+    /// The refresh was requested by the client.
+    pub const CLIENT: Self = Self::new(rxegy_sys::XRQ_CLIENT);
+    /// The refresh rolls the instrument over to a new trading day.
+    pub const START_OF_DAY_ROLL: Self = Self::new(rxegy_sys::XRQ_SODROLL);
+    /// The refresh follows a cancellation or correction.
+    pub const CANCEL_CORRECTION: Self = Self::new(rxegy_sys::XRQ_CANCOR);
+    /// The refresh is the initial image of a new subscription.
+    pub const SUBSCRIPTION: Self = Self::new(rxegy_sys::XRQ_SUBSCR);
+    /// The refresh carries the official closing price.
+    pub const OFFICIAL_CLOSE: Self = Self::new(rxegy_sys::XRQ_OFCLCLOSE);
+    /// The refresh carries the end-of-day closing price.
+    pub const END_OF_DAY_CLOSE: Self = Self::new(rxegy_sys::XRQ_EODCLOSE);
+    /// The refresh carries the start-of-day opening price.
+    pub const START_OF_DAY_OPEN: Self = Self::new(rxegy_sys::XRQ_SODOPEN);
+    /// The refresh carries an off-market update.
+    pub const OFF_MARKET: Self = Self::new(rxegy_sys::XRQ_OFFMKT);
+    /// The refresh carries the official opening price.
+    pub const OFFICIAL_OPEN: Self = Self::new(rxegy_sys::XRQ_OFCLOPEN);
+    /// The refresh clears the book.
+    pub const CLEAR_BOOK: Self = Self::new(rxegy_sys::XRQ_CLEARBOOK);
+    /// The refresh carries static reference data.
+    pub const STATIC_DATA: Self = Self::new(rxegy_sys::XRQ_STATICDATA);
+    /// The refresh was manually published.
+    pub const MANUAL: Self = Self::new(rxegy_sys::XRQ_MANUAL);
+    /// The refresh was generated by the appliance.
+    pub const GENERATED: Self = Self::new(rxegy_sys::XRQ_GENERATED);
+    /// The refresh changes the trade date.
+    pub const TRADE_DATE: Self = Self::new(rxegy_sys::XRQ_TRADE_DATE);
+    /// The refresh carries the last known values.
+    pub const LAST_KNOWN: Self = Self::new(rxegy_sys::XRQ_LAST_KNOWN);
+    /// The refresh is for a newly listed instrument.
+    pub const NEW: Self = Self::new(rxegy_sys::XRQ_NEW);
+    /// The refresh is a snapshot.
+    pub const SNAPSHOT: Self = Self::new(rxegy_sys::XRQ_SNAPSHOT);
+    /// The refresh follows a change of primary exchange.
+    pub const PRIMARY_EXCHANGE_CHANGE: Self = Self::new(rxegy_sys::XRQ_PRIMEX_CHNG);
+    /// The refresh follows a recomputation of the composite view.
+    pub const COMPOSITE_RECOMPUTE: Self = Self::new(rxegy_sys::XRQ_COMPOSITE_RECOMPUTE);
+    /// The qualifier is not valid.
+    pub const INVALID: Self = Self::new(rxegy_sys::XRQ_INVAL);
+}
+
+impl RefreshQualifiers {
+    /// Whether the refresh is the initial image of a subscription, or was requested by the client.
+    pub fn is_initial(&self) -> bool {
+        self.contains_any(&[RefreshQualifier::SUBSCRIPTION, RefreshQualifier::CLIENT])
+    }
+
+    /// Whether the refresh follows a cancellation or correction.
+    pub fn is_cancel_correction(&self) -> bool {
+        self.contains(RefreshQualifier::CANCEL_CORRECTION)
+    }
+
+    /// Whether the refresh carries an official or end-of-day closing price.
+    pub fn is_close(&self) -> bool {
+        self.contains_any(&[
+            RefreshQualifier::OFFICIAL_CLOSE,
+            RefreshQualifier::END_OF_DAY_CLOSE,
+        ])
+    }
+
+    /// Whether the refresh carries an official or start-of-day opening price.
+    pub fn is_open(&self) -> bool {
+        self.contains_any(&[
+            RefreshQualifier::OFFICIAL_OPEN,
+            RefreshQualifier::START_OF_DAY_OPEN,
+        ])
+    }
+
+    /// Whether the refresh rolls the instrument over to a new trading day.
+    pub fn is_day_roll(&self) -> bool {
+        self.contains_any(&[
+            RefreshQualifier::START_OF_DAY_ROLL,
+            RefreshQualifier::TRADE_DATE,
+        ])
+    }
+
+    /// Whether the refresh clears the book.
+    pub fn is_clear_book(&self) -> bool {
+        self.contains(RefreshQualifier::CLEAR_BOOK)
+    }
+}
+
+/// The direction of a price relative to the previous price
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct TickFlags(XC_TICK_FLAGS);
+
+impl TickFlags {
+    /// Whether the price ticked up from the previous different price.
+    pub fn is_uptick(&self) -> bool {
+        self.0.xtf_tick_up() != 0
+    }
+
+    /// Whether the price ticked down from the previous different price.
+    pub fn is_downtick(&self) -> bool {
+        self.0.xtf_tick_down() != 0
+    }
+
+    /// Whether the price is up from the previous price.
+    pub fn is_price_up(&self) -> bool {
+        self.0.xtf_price_up() != 0
+    }
+
+    /// Whether the price is down from the previous price.
+    pub fn is_price_down(&self) -> bool {
+        self.0.xtf_price_down() != 0
+    }
+}
+
+impl From<XC_TICK_FLAGS> for TickFlags {
+    fn from(value: XC_TICK_FLAGS) -> Self {
+        Self(value)
+    }
+}
+
+/// The statistics an equity trade updates
+///
+/// XCAPI only flags the price statistics a trade updates; there is no flag for volume, so there is
+/// no `updates_volume()` method. Whether a trade counted toward volume shows in the accumulated
+/// totals carried by every trade instead: its [`volume`](crate::event::EquityTradeData::volume)
+/// grows by the trade's size if so, and its
+/// [`vwap_volume`](crate::event::EquityTradeData::vwap_volume) does if it also counted toward the
+/// VWAP. Deriving eligibility from the trade qualifiers would only repeat rules which differ by
+/// feed, and which the appliance has already applied.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct TradeUpdateFlags(XC_EQUITY_TRADE_UPDATE_FLAGS);
+
+impl TradeUpdateFlags {
+    /// Whether the trade updates the last price.
+    pub fn updates_last(&self) -> bool {
+        self.0.update_last() != 0
+    }
+
+    /// Whether the trade updates the opening price.
+    pub fn updates_open(&self) -> bool {
+        self.0.update_open() != 0
+    }
+
+    /// Whether the trade updates the high price.
+    pub fn updates_high(&self) -> bool {
+        self.0.update_high() != 0
+    }
+
+    /// Whether the trade updates the low price.
+    pub fn updates_low(&self) -> bool {
+        self.0.update_low() != 0
+    }
+
+    /// Whether the trade updates the closing price.
+    pub fn updates_close(&self) -> bool {
+        self.0.update_close() != 0
+    }
+}
+
+impl From<XC_EQUITY_TRADE_UPDATE_FLAGS> for TradeUpdateFlags {
+    fn from(value: XC_EQUITY_TRADE_UPDATE_FLAGS) -> Self {
+        Self(value)
+    }
+}
@@ -1,6 +1,7 @@
 //! Session Objects
 
 use crate::{
+    container::{Owner, Registry, SuspendGuard, Teardown},
     error::{Error, ExegyError, Result, Success},
     event::Common,
     field::{self, Field as FieldTrait},
@@ -12,11 +13,13 @@ use secrecy::{ExposeSecret, SecretString};
 use std::{
     ffi::{CString, c_void},
     fmt::{Display, Formatter, Result as FmtResult},
+    mem::ManuallyDrop,
     net::{SocketAddr, ToSocketAddrs},
     path::{Path, PathBuf},
     process,
     ptr::{self, NonNull},
     result::Result as StdResult,
+    sync::{Arc, Mutex},
 };
 
 /// A callback object containting status event details
@@ -28,6 +31,72 @@ impl_wrapper_on_newtype!(StatusEvent, ObjectKind::EventSessionStatus);
 
 impl Common for StatusEvent {}
 
+/// An enumeration of the connection lifecycle states a session can be in.
+///
+/// Session states are decoded from the status code delivered with each session status event.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SessionState {
+    /// The session is attempting to connect to an appliance.
+    Connecting,
+    /// The connection to the appliance has been re-established, and normal processing has resumed.
+    Connected,
+    /// The session is connected to an appliance, and the user has been logged in.
+    LoggedIn,
+    /// The session has been disconnected, and automatic reconnection is disabled.
+    ///
+    /// The reason for the disconnection is available from [`Common::status()`] on the event.
+    Disconnected,
+    /// The session has been disconnected, and XCAPI is attempting to reconnect.
+    Reconnecting,
+    /// The session has failed in a manner which cannot be recovered by reconnecting.
+    Failed(ExegyError),
+    /// A replay session has delivered all of its events.
+    ReplayComplete,
+}
+
+impl SessionState {
+    /// Decode the status code of a session status event into a session state.
+    pub(crate) fn from_status(status: xerr, reconnect_enabled: bool) -> Self {
+        match status {
+            rxegy_sys::XSUCCESS => Self::LoggedIn,
+            rxegy_sys::XCOMPLETE | rxegy_sys::XEND => Self::ReplayComplete,
+            rxegy_sys::XPENDING | rxegy_sys::XWOULDBLOCK => Self::Connecting,
+            status if rxegy_sys::xerr_is_good(status) => Self::Connected,
+            status => {
+                let error = ExegyError::try_from(status).unwrap_or(ExegyError::Error);
+                match error {
+                    ExegyError::ConnectionReset
+                    | ExegyError::ConnectionRefused
+                    | ExegyError::NoLink
+                    | ExegyError::NotConnected
+                    | ExegyError::Communication
+                    | ExegyError::Socket
+                    | ExegyError::Timeout
+                    | ExegyError::Time
+                    | ExegyError::Down
+                    | ExegyError::SlowConsumer => {
+                        if reconnect_enabled {
+                            Self::Reconnecting
+                        } else {
+                            Self::Disconnected
+                        }
+                    }
+                    error => Self::Failed(error),
+                }
+            }
+        }
+    }
+
+    /// Whether the session is currently usable for subscriptions.
+    pub fn is_up(&self) -> bool {
+        matches!(
+            self,
+            Self::Connected | Self::LoggedIn | Self::ReplayComplete
+        )
+    }
+}
+
 /// The ticker session object
 #[derive(Debug)]
 pub struct TickerSession(NonNull<c_void>);
@@ -36,13 +105,129 @@ impl_wrapper_on_newtype!(TickerSession, ObjectKind::SessionTicker);
 
 impl Session for TickerSession {}
 
+impl Drop for TickerSession {
+    /// Release this session's hold on the XCAPI session.
+    ///
+    /// Containers created on this session keep it alive, so the session is only disconnected and
+    /// destroyed once it and every container created on it have been dropped. Like containers,
+    /// sessions may not be dropped from within a callback.
+    fn drop(&mut self) {
+        match self.context() {
+            Ok(context) => {
+                let lifeline = match context.lifeline.lock() {
+                    Ok(mut guard) => guard.take(),
+                    Err(poisoned) => poisoned.into_inner().take(),
+                };
+
+                // This may destroy the session and free the context
+                drop(lifeline);
+            }
+            Err(e) => tracing::error!("Could not release session: {}", e),
+        }
+    }
+}
+
 impl TickerSession {
+    /// Retrieve the most recent connection lifecycle state of this session.
+    pub fn state(&self) -> Result<SessionState> {
+        self.context()?.state()
+    }
+
     /// Retrieve the turnkey value of this session
-    #[allow(dead_code)]
     fn turnkey(&self) -> Result<u64> {
         field::get_u64(self, rxegy_sys::XC_SESSION, Field::Turnkey)
     }
 
+    /// Retrieve a reference to the context object this session was created with.
+    fn context(&self) -> Result<&Context> {
+        let ptr = self.turnkey()? as *const Context;
+        // SAFETY: the turnkey is set to a leaked context box when the session is created.
+        unsafe { ptr.as_ref() }.ok_or(Error::SessionNotInitialized)
+    }
+
+    /// Retrieve the registry of containers created on this session.
+    pub(crate) fn registry(&self) -> Result<&Arc<Registry>> {
+        Ok(&self.context()?.containers)
+    }
+
+    /// Register a newly created container, returning the owner which will destroy it and keep
+    /// this session alive until it does.
+    pub(crate) fn register(&self, handle: xhandle, teardown: Teardown) -> Result<Owner> {
+        let context = self.context()?;
+        let lifeline = context
+            .lifeline
+            .lock()
+            .map_err(|_e| Error::SessionPanic)?
+            .clone()
+            .ok_or(Error::SessionNotInitialized)?;
+
+        context.containers.register(handle, teardown, lifeline)
+    }
+
+    /// Suspend callbacks on every container created on this session, until the returned guard is
+    /// dropped.
+    ///
+    /// Items continue to be updated and subscriptions are kept while callbacks are suspended, so a
+    /// universe can be rebuilt without events being delivered part way through. Containers created
+    /// after this call are not suspended. This may not be called from within a callback.
+    pub fn suspend_callbacks(&self) -> Result<SuspendGuard<'_>> {
+        let registry = self.registry()?;
+        let mut guard = SuspendGuard::pinned(Arc::clone(registry));
+
+        // Any containers already suspended are resumed when the guard is dropped on failure
+        for handle in registry.handles() {
+            guard.suspend(handle)?;
+        }
+
+        Ok(guard)
+    }
+
+    /// Resume callbacks on every container created on this session.
+    ///
+    /// This may not be called from within a callback.
+    pub fn resume_callbacks(&self) -> Result<()> {
+        let registry = self.registry()?;
+        let mut guard = SuspendGuard::pinned(Arc::clone(registry));
+        guard.extend(registry.handles());
+        guard.resume()
+    }
+
+    /// Migrate this session to a new set of Exegy appliances, or login with new credentials.
+    ///
+    /// Existing containers and their subscribed slots are preserved across the migration, so an
+    /// appliance can be drained for maintenance without tearing down subscriptions. This function
+    /// wraps [xcMigrateSession](rxegy_sys::xcMigrateSession), and is synchronous: it will not
+    /// return until the session has been re-established on one of the new servers, or every
+    /// server in the list has been tried once.
+    ///
+    /// Note that it is possible to leave the session in a disconnected state if none of the new
+    /// servers can be reached, in which case this method should be called again with a different
+    /// server list.
+    pub fn migrate_to(&self, migration: Migration) -> Result<()> {
+        tracing::trace!("Migrating tickerplant session");
+
+        let server_list = migration.server_list.to_cstring()?;
+        let username = CString::new(migration.username)?;
+        let password = CString::new(migration.password.expose_secret())?;
+
+        let context = self.context()?;
+        context.begin_migration(self, migration.progress, migration.server_list)?;
+
+        let status = unsafe {
+            rxegy_sys::xcMigrateSession(
+                self.as_xhandle(),
+                server_list.as_ptr(),
+                username.as_ptr(),
+                password.as_ptr(),
+            )
+        };
+
+        let retval = Success::try_from(status).map(|_success| ());
+        context.finish_migration(self, retval);
+
+        Ok(retval?)
+    }
+
     /// Retrieve the maximum depth of price-book containers created on this session.
     pub fn max_pricebook_depth(&self) -> Result<u16> {
         field::get_u16(self, rxegy_sys::XC_SESSION, TickerField::MaxPriceBookDepth)
@@ -263,13 +448,132 @@ pub trait Session: Wrapper {
     }
 }
 
-/// The callback prototype for status events
-pub type TickerStatusEventFn = fn(&TickerSession, &StatusEvent) -> Result<bool>;
+/// The callback prototype for status events.
+///
+/// The callback receives the raw status event, along with the decoded [`SessionState`]. The
+/// returned boolean indicates whether automatic reconnection should remain enabled: returning
+/// `Ok(false)` will disable reconnection on the session (leaving it disconnected after the next
+/// connection failure), while `Ok(true)` leaves the reconnection setting untouched. Errors are
+/// logged and otherwise ignored.
+pub type TickerStatusEventFn = fn(&TickerSession, &StatusEvent, &SessionState) -> Result<bool>;
+
+/// The callback prototype for session migration progress events
+pub type TickerMigrationEventFn = fn(&TickerSession, &MigrationStatus) -> Result<()>;
+
+/// An enumeration of the progress states of a live session migration.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MigrationStatus {
+    /// The migration has been requested, and the existing connection is about to be torn down.
+    Started,
+    /// The session has been disconnected from its previous appliance.
+    Disconnected,
+    /// The session has connected to a new appliance.
+    Connected,
+    /// The migration completed successfully, existing containers and slots have been preserved.
+    Completed,
+    /// The migration failed, and the session may be left in a disconnected state.
+    Failed(ExegyError),
+}
+
+/// A set of servers and credentials a session should be migrated to.
+#[derive(Default)]
+pub struct Migration {
+    server_list: ServerList,
+    username: String,
+    password: SecretString,
+    progress: Option<TickerMigrationEventFn>,
+}
+
+impl Migration {
+    /// Set the servers to migrate to.
+    pub fn servers(mut self, servers: ServerList) -> Self {
+        self.server_list = servers;
+        self
+    }
+
+    /// Set the username to use when connecting to the new servers.
+    pub fn username(mut self, username: &str) -> Self {
+        self.username = username.to_string();
+        self
+    }
+
+    /// Set the password to use when connecting to the new servers.
+    pub fn password(mut self, password: &SecretString) -> Self {
+        self.password = password.clone();
+        self
+    }
+
+    /// Set the callback to be fired as the migration progresses.
+    ///
+    /// The callback will be fired when the migration starts, as the old connection is torn down,
+    /// as the new connection is established, and finally when the migration completes or fails.
+    /// Connection changes outside of a migration, such as ordinary reconnects, are not reported.
+    pub fn on_progress(mut self, func: TickerMigrationEventFn) -> Self {
+        self.progress = Some(func);
+        self
+    }
+}
+
+/// An ordered list of servers a session should connect to.
+#[derive(Clone, Debug, Default)]
+pub struct ServerList(Vec<Server>);
+
+impl ServerList {
+    /// Create an empty server list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an XTI file to inject into the session.
+    pub fn add_xti(mut self, xti: &Path) -> Self {
+        self.0.push(Server::Xti(xti.to_owned()));
+        self
+    }
+
+    /// Add an RoCE1/Infiniband address to connect to.
+    pub fn add_ib<I: ToString>(mut self, ib: &I) -> Self {
+        self.0.push(Server::Infiniband(ib.to_string()));
+        self
+    }
+
+    /// Add a RoCE2 address to connect to.
+    pub fn add_roce<R: ToString>(mut self, roce: &R) -> Self {
+        self.0.push(Server::RoCE(roce.to_string()));
+        self
+    }
+
+    /// Add a server to connect to
+    pub fn add_server(mut self, server: &str) -> Result<Self> {
+        server
+            .to_socket_addrs()?
+            .for_each(|addr| self.0.push(Server::Ip(addr)));
+        Ok(self)
+    }
+
+    /// Build the comma-separated server list string XCAPI expects.
+    fn to_cstring(&self) -> Result<CString> {
+        Ok(CString::new(self.to_string())?)
+    }
+}
+
+impl Display for ServerList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for (index, server) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", server)?;
+        }
+
+        Ok(())
+    }
+}
 
 /// A session builder
 #[derive(Default)]
 pub struct Builder {
-    server_list: Vec<Server>,
+    server_list: ServerList,
     username: String,
     password: SecretString,
     cb_affinity: Option<u64>,
@@ -293,29 +597,33 @@ impl Builder {
         self
     }
 
+    /// Set the servers to connect to, replacing any added so far.
+    pub fn servers(mut self, servers: ServerList) -> Self {
+        self.server_list = servers;
+        self
+    }
+
     /// Add an XTI file to inject into this session.
     pub fn add_xti(mut self, xti: &Path) -> Self {
-        self.server_list.push(Server::Xti(xti.to_owned()));
+        self.server_list = self.server_list.add_xti(xti);
         self
     }
 
     /// Add an RoCE1/Infiniband address to connect to.
     pub fn add_ib<I: ToString>(mut self, ib: &I) -> Self {
-        self.server_list.push(Server::Infiniband(ib.to_string()));
+        self.server_list = self.server_list.add_ib(ib);
         self
     }
 
     /// Add a RoCE2 address to connect to.
     pub fn add_roce<R: ToString>(mut self, roce: &R) -> Self {
-        self.server_list.push(Server::RoCE(roce.to_string()));
+        self.server_list = self.server_list.add_roce(roce);
         self
     }
 
     /// Add a server to connect to
     pub fn add_server(mut self, server: &str) -> Result<Self> {
-        server
-            .to_socket_addrs()?
-            .for_each(|addr| self.server_list.push(Server::Ip(addr)));
+        self.server_list = self.server_list.add_server(server)?;
         Ok(self)
     }
 
@@ -376,13 +684,7 @@ impl Builder {
         status_fn: StatusFn,
     ) -> Result<T> {
         // Build our parameters
-        let server_list = CString::new(
-            self.server_list
-                .iter()
-                .map(ToString::to_string)
-                .collect::<Vec<String>>()
-                .join(","),
-        )?;
+        let server_list = self.server_list.to_cstring()?;
         let username = CString::new(self.username)?;
         let password = CString::new(self.password.expose_secret())?;
 
@@ -392,6 +694,10 @@ impl Builder {
             affinity: self.cb_affinity,
             priority: self.cb_priority.map(|v| v as u32),
             market_events_per_instrument,
+            migration: Mutex::new(None),
+            state: Mutex::new(SessionState::Connecting),
+            containers: Arc::new(Registry::new()),
+            lifeline: Mutex::new(None),
         });
 
         let turnkey = Box::into_raw(context) as u64;
@@ -408,8 +714,24 @@ impl Builder {
                 password.as_ptr(),
             );
 
-            Success::try_from(status)?;
-            T::from_xhandle_and_type(handle, kind as u16)?
+            if let Err(e) = Success::try_from(status) {
+                // Don't leak the context if we failed to create the session
+                let _context = Box::from_raw(turnkey as *mut Context);
+                return Err(e.into());
+            }
+
+            let lifeline = Lifeline {
+                handle: handle as usize,
+                turnkey,
+            };
+
+            // Dropping the lifeline destroys the session and frees the context on failure
+            let session = T::from_xhandle_and_type(handle, kind as u16)?;
+
+            let context = &*(turnkey as *const Context);
+            *context.lifeline.lock().map_err(|_e| Error::SessionPanic)? = Some(Arc::new(lifeline));
+
+            session
         };
 
         if let Some(affin) = self.bg_affinity {
@@ -572,7 +894,7 @@ impl FieldTrait for TickerField {
 }
 
 /// A representation of all the various address types supported by XCAPI
-#[derive(Clone, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 enum Server {
     Xti(PathBuf),
     Infiniband(String),
@@ -608,15 +930,143 @@ struct Context {
     priority: Option<u32>,
     /// Whether to fire market event callbacks per instrument
     market_events_per_instrument: Option<bool>,
+    /// The progress of the migration underway, if any
+    migration: Mutex<Option<MigrationProgress>>,
+    /// The most recent state reported by a status event
+    state: Mutex<SessionState>,
+    /// The containers created on this session which are still alive
+    containers: Arc<Registry>,
+    /// The session's own hold on itself, released when the session is dropped
+    lifeline: Mutex<Option<Arc<Lifeline>>>,
 }
 
-impl Drop for Context {
+/// The XCAPI session and its context, which are destroyed once the session and every container
+/// created on it have been dropped.
+#[derive(Debug)]
+pub(crate) struct Lifeline {
+    handle: usize,
+    turnkey: u64,
+}
+
+impl Drop for Lifeline {
     fn drop(&mut self) {
-        tracing::error!("Drop called on context, this shouldn't happen.")
+        let context = self.turnkey as *mut Context;
+
+        // SAFETY: the context outlives every holder of the lifeline, and is only freed here.
+        unsafe { &*context }.containers.teardown_all();
+
+        let mut handle = self.handle as xhandle;
+        if let Err(e) = Success::try_from(unsafe { rxegy_sys::xcDestroyObject(&mut handle) }) {
+            // The session may still be firing callbacks, so leak the context rather than free it
+            tracing::error!("Could not destroy session: {}", e);
+            return;
+        }
+
+        let _context = unsafe { Box::from_raw(context) };
     }
 }
 
 impl Context {
+    /// Retrieve a copy of the most recent session state.
+    fn state(&self) -> Result<SessionState> {
+        Ok(self.state.lock().map_err(|_e| Error::SessionPanic)?.clone())
+    }
+
+    /// Record a new session state.
+    fn set_state(&self, state: SessionState) -> Result<()> {
+        *self.state.lock().map_err(|_e| Error::SessionPanic)? = state;
+        Ok(())
+    }
+
+    /// Record the start of a migration to the given servers, and report it.
+    fn begin_migration(
+        &self,
+        session: &TickerSession,
+        func: Option<TickerMigrationEventFn>,
+        targets: ServerList,
+    ) -> Result<()> {
+        tracing::debug!(%targets, "Migration started");
+        *self.migration.lock().map_err(|_e| Error::SessionPanic)? = Some(MigrationProgress {
+            func,
+            targets,
+            phase: MigrationPhase::Started,
+        });
+        report_migration(func, session, MigrationStatus::Started);
+        Ok(())
+    }
+
+    /// Advance the migration underway, if any, with a new session state, reporting the
+    /// transition if the state moves the migration to a new phase.
+    fn advance_migration(&self, session: &TickerSession, state: &SessionState) {
+        let mut guard = match self.migration.lock() {
+            Ok(guard) => guard,
+            Err(_e) => {
+                tracing::warn!("Migration progress lock was poisoned");
+                return;
+            }
+        };
+
+        let Some(progress) = guard.as_mut() else {
+            return;
+        };
+
+        let transition = match (progress.phase, state) {
+            (MigrationPhase::Finished, _) => None,
+            (MigrationPhase::Started | MigrationPhase::Disconnected, state) if state.is_up() => {
+                tracing::debug!(
+                    server = session.server_name().ok(),
+                    targets = %progress.targets,
+                    "Migration connected"
+                );
+                Some((MigrationPhase::Connected, MigrationStatus::Connected))
+            }
+            (_, SessionState::Failed(error)) => {
+                Some((MigrationPhase::Finished, MigrationStatus::Failed(*error)))
+            }
+            (_, SessionState::Disconnected) => Some((
+                MigrationPhase::Finished,
+                MigrationStatus::Failed(ExegyError::NotConnected),
+            )),
+            (
+                MigrationPhase::Started | MigrationPhase::Connected,
+                SessionState::Connecting | SessionState::Reconnecting,
+            ) => Some((MigrationPhase::Disconnected, MigrationStatus::Disconnected)),
+            _ => None,
+        };
+
+        if let Some((phase, status)) = transition {
+            progress.phase = phase;
+            let func = progress.func;
+            drop(guard);
+            report_migration(func, session, status);
+        }
+    }
+
+    /// End the migration underway, reporting its outcome unless a failure was already reported.
+    fn finish_migration(&self, session: &TickerSession, result: StdResult<(), ExegyError>) {
+        let progress = match self.migration.lock() {
+            Ok(mut guard) => guard.take(),
+            Err(_e) => {
+                tracing::warn!("Migration progress lock was poisoned");
+                return;
+            }
+        };
+
+        let Some(progress) = progress else {
+            return;
+        };
+
+        if progress.phase == MigrationPhase::Finished {
+            return;
+        }
+
+        let status = match result {
+            Ok(()) => MigrationStatus::Completed,
+            Err(error) => MigrationStatus::Failed(error),
+        };
+        report_migration(progress.func, session, status);
+    }
+
     fn dispatch(
         &self,
         handle: xhandle,
@@ -626,9 +1076,10 @@ impl Context {
     ) -> Result<()> {
         tracing::trace_span!("rxegy::session::Context::dispatch");
 
-        // Grab the session handle
+        // Grab the session handle, without destroying it when we're done
         // TODO: support monitoring sessions
-        let session = TickerSession::from_xhandle(handle)?;
+        let session = ManuallyDrop::new(TickerSession::from_xhandle(handle)?);
+        let session: &TickerSession = &session;
 
         tracing::trace!("Session = {:?}", session);
 
@@ -637,7 +1088,7 @@ impl Context {
             if let Some(affinity) = self.affinity {
                 tracing::debug!(affinity = affinity, "Setting callback thread affinity");
                 if let Err(e) = field::set_u64(
-                    &session,
+                    session,
                     rxegy_sys::XC_SESSION,
                     Field::ClientAffinityMask,
                     affinity,
@@ -649,7 +1100,7 @@ impl Context {
             if let Some(prio) = self.priority {
                 tracing::debug!(priority = prio, "Setting callback thread priority");
                 if let Err(e) = field::set_u32(
-                    &session,
+                    session,
                     rxegy_sys::XC_SESSION,
                     Field::ClientThreadPriority,
                     prio,
@@ -664,7 +1115,7 @@ impl Context {
                     "Configuring market events per instrument"
                 );
                 if let Err(e) = field::set_u8(
-                    &session,
+                    session,
                     rxegy_sys::XC_SESSION,
                     TickerField::MarketStatusCallbacks,
                     enable as u8,
@@ -677,9 +1128,19 @@ impl Context {
         match event_type {
             val if val == EventKind::Status as u16 => {
                 let event = StatusEvent::from_xhandle_and_type(event_handle, event_type)?;
+                let state =
+                    SessionState::from_status(status, session.reconnect_enabled().unwrap_or(true));
+                tracing::debug!(?state, "Session state changed");
+                self.set_state(state.clone())?;
+
+                self.advance_migration(session, &state);
+
                 match self.status {
                     StatusFn::Ticker(func) => {
-                        let _value = func(&session, &event)?;
+                        if !func(session, &event, &state)? {
+                            tracing::debug!("Status callback requested reconnection be disabled");
+                            session.disable_reconnect()?;
+                        }
                     }
                     StatusFn::None => {}
                 }
@@ -693,6 +1154,43 @@ impl Context {
     }
 }
 
+/// The progress of a migration underway on a session
+struct MigrationProgress {
+    /// The callback to fire as the migration progresses
+    func: Option<TickerMigrationEventFn>,
+    /// The servers the session is being migrated to
+    targets: ServerList,
+    /// The most recent phase the migration has reached
+    phase: MigrationPhase,
+}
+
+/// An enumeration of the phases a migration moves through
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum MigrationPhase {
+    /// The migration has been requested
+    Started,
+    /// The session has been disconnected from its previous appliance
+    Disconnected,
+    /// The session has connected to a new appliance
+    Connected,
+    /// The migration has failed, and the failure has already been reported
+    Finished,
+}
+
+/// Report migration progress to the user's callback, if one was given.
+fn report_migration(
+    func: Option<TickerMigrationEventFn>,
+    session: &TickerSession,
+    status: MigrationStatus,
+) {
+    if let Some(func) = func {
+        tracing::debug!(?status, "Reporting migration progress");
+        if let Err(e) = func(session, &status) {
+            tracing::debug!("Migration callback returned an error: {}", e);
+        }
+    }
+}
+
 #[unsafe(no_mangle)]
 unsafe extern "C" fn _rxegy_session_callback(
     handle: xhandle,
@@ -0,0 +1,508 @@
+//! Latency Statistics
+//!
+//! Aggregates the latency of each stage of event delivery into histograms, per feed and data
+//! source line, so measurements can be taken without doing any work beyond a histogram update in
+//! the callback.
+
+use crate::{FeedId, LatencyBreakdown, error::Result, event::Common, timing::EventTiming};
+use hdrhistogram::Histogram;
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex, MutexGuard, PoisonError, RwLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime},
+};
+
+/// The largest latency recorded without saturating, one minute in nanoseconds.
+const MAX_LATENCY_NANOS: u64 = 60_000_000_000;
+
+/// The number of significant decimal digits each histogram preserves.
+const SIGNIFICANT_DIGITS: u8 = 3;
+
+/// A stage of event delivery
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Hop {
+    /// From the exchange timestamp to receipt by the appliance.
+    ExchangeToReceive,
+    /// From receipt by the appliance to transmission by the appliance.
+    ReceiveToTransmit,
+    /// From transmission by the appliance to receipt by the XCAPI library.
+    TransmitToXcapiReceive,
+    /// From receipt by the XCAPI library to the callback being fired.
+    XcapiReceiveToCallback,
+    /// From the exchange timestamp to the callback being fired.
+    ExchangeToCallback,
+}
+
+impl Hop {
+    /// Every stage, in delivery order.
+    pub const ALL: [Hop; 5] = [
+        Hop::ExchangeToReceive,
+        Hop::ReceiveToTransmit,
+        Hop::TransmitToXcapiReceive,
+        Hop::XcapiReceiveToCallback,
+        Hop::ExchangeToCallback,
+    ];
+
+    fn select(self, breakdown: &LatencyBreakdown) -> Option<i64> {
+        let delta = match self {
+            Hop::ExchangeToReceive => breakdown.exchange_to_receive,
+            Hop::ReceiveToTransmit => breakdown.receive_to_transmit,
+            Hop::TransmitToXcapiReceive => breakdown.transmit_to_xcapi_receive,
+            Hop::XcapiReceiveToCallback => breakdown.xcapi_receive_to_callback,
+            Hop::ExchangeToCallback => breakdown.exchange_to_callback,
+        };
+
+        delta.map(i64::from)
+    }
+}
+
+/// The histogram of a single stage, along with the samples it could not hold
+#[derive(Clone, Debug)]
+struct HopHistogram {
+    histogram: Histogram<u64>,
+    negative: u64,
+}
+
+impl HopHistogram {
+    fn new() -> Self {
+        Self {
+            histogram: Histogram::new_with_bounds(1, MAX_LATENCY_NANOS, SIGNIFICANT_DIGITS)
+                .expect("Constant histogram bounds are valid"),
+            negative: 0,
+        }
+    }
+
+    fn record(&mut self, nanos: i64) {
+        match u64::try_from(nanos) {
+            Ok(nanos) => self.histogram.saturating_record(nanos),
+            // The exchange's clock is not synchronized with the appliance's
+            Err(_) => self.negative += 1,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.histogram.reset();
+        self.negative = 0;
+    }
+
+    fn snapshot(&self) -> HopSnapshot {
+        let histogram = &self.histogram;
+        HopSnapshot {
+            count: histogram.len(),
+            negative: self.negative,
+            min: Duration::from_nanos(histogram.min()),
+            max: Duration::from_nanos(histogram.max()),
+            mean: Duration::from_nanos(histogram.mean() as u64),
+            p50: Duration::from_nanos(histogram.value_at_quantile(0.50)),
+            p90: Duration::from_nanos(histogram.value_at_quantile(0.90)),
+            p99: Duration::from_nanos(histogram.value_at_quantile(0.99)),
+            p999: Duration::from_nanos(histogram.value_at_quantile(0.999)),
+        }
+    }
+}
+
+/// The histograms of every stage for a single feed and line
+#[derive(Clone, Debug)]
+struct LineHistograms {
+    hops: [HopHistogram; 5],
+    /// The number of events recorded since the histograms were last reset.
+    events: u64,
+}
+
+impl LineHistograms {
+    fn new() -> Self {
+        Self {
+            hops: std::array::from_fn(|_| HopHistogram::new()),
+            events: 0,
+        }
+    }
+
+    fn record(&mut self, breakdown: &LatencyBreakdown) {
+        self.events += 1;
+        for (hop, histogram) in Hop::ALL.iter().zip(self.hops.iter_mut()) {
+            if let Some(nanos) = hop.select(breakdown) {
+                histogram.record(nanos);
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.events = 0;
+        for histogram in &mut self.hops {
+            histogram.reset();
+        }
+    }
+
+    fn snapshot(&self, (feed, line): (FeedId, u16)) -> LineSnapshot {
+        LineSnapshot {
+            feed,
+            line,
+            hops: std::array::from_fn(|hop| self.hops[hop].snapshot()),
+        }
+    }
+}
+
+/// A summary of the latency of a single stage, at the time of a snapshot
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct HopSnapshot {
+    /// The number of samples recorded.
+    pub count: u64,
+    /// The number of samples which were negative, and so not recorded.
+    pub negative: u64,
+    /// The smallest sample.
+    pub min: Duration,
+    /// The largest sample.
+    pub max: Duration,
+    /// The mean of every sample.
+    pub mean: Duration,
+    /// The median sample.
+    pub p50: Duration,
+    /// The 90th percentile sample.
+    pub p90: Duration,
+    /// The 99th percentile sample.
+    pub p99: Duration,
+    /// The 99.9th percentile sample.
+    pub p999: Duration,
+}
+
+/// A summary of the latency of every stage for a single feed and line
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct LineSnapshot {
+    /// The feed the events were published on.
+    pub feed: FeedId,
+    /// The data source line the events were received on.
+    pub line: u16,
+    /// The summary of each stage, in the order of [`Hop::ALL`].
+    pub hops: [HopSnapshot; 5],
+}
+
+impl LineSnapshot {
+    /// Retrieve the summary of the given stage.
+    pub fn hop(&self, hop: Hop) -> &HopSnapshot {
+        &self.hops[hop as usize]
+    }
+}
+
+/// A summary of the latency of every feed and line, at a point in time
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct Snapshot {
+    /// The time the snapshot was taken.
+    pub taken_at: SystemTime,
+    /// The summary of each feed and line which has recorded an event.
+    pub lines: Vec<LineSnapshot>,
+}
+
+/// Latency histograms of every stage of event delivery, per feed and line
+///
+/// Each line has its own lock, so recording on one line never waits for another, and the map of
+/// lines is only locked exclusively when a line records its first event.
+#[derive(Debug, Default)]
+pub struct LatencyStats(RwLock<HashMap<(FeedId, u16), Mutex<LineHistograms>>>);
+
+/// Lock a single line's histograms.
+fn lock(line: &Mutex<LineHistograms>) -> MutexGuard<'_, LineHistograms> {
+    line.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl LatencyStats {
+    /// Create an empty set of histograms.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the latency of an event received on the given feed and line.
+    pub fn record(&self, feed: FeedId, line: u16, timing: &EventTiming) {
+        self.record_breakdown(feed, line, &timing.latency_breakdown());
+    }
+
+    /// Record the latency of each stage of an event received on the given feed and line.
+    fn record_breakdown(&self, feed: FeedId, line: u16, breakdown: &LatencyBreakdown) {
+        if let Some(histograms) = self
+            .0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&(feed, line))
+        {
+            lock(histograms).record(breakdown);
+            return;
+        }
+
+        self.0
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry((feed, line))
+            .or_insert_with(|| Mutex::new(LineHistograms::new()))
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .record(breakdown);
+    }
+
+    /// Record the latency of an event, using the feed of its item key and the line it was
+    /// received on.
+    pub fn record_event<E: Common>(&self, event: &E) -> Result<()> {
+        let feed = *event.item_key()?.feed_id();
+        let line = event.line_id()?;
+        let timing = event.timing()?;
+        self.record(feed, line, &timing);
+        Ok(())
+    }
+
+    /// Summarize every histogram, leaving them untouched.
+    pub fn snapshot(&self) -> Snapshot {
+        self.summarize(|_histograms| {})
+    }
+
+    /// Summarize every histogram, then clear them so the next snapshot covers a new interval.
+    ///
+    /// The histograms are reset in place, so recording never has to allocate them again.
+    pub fn take_snapshot(&self) -> Snapshot {
+        self.summarize(LineHistograms::reset)
+    }
+
+    /// Summarize each line in turn, then apply the given function to its histograms.
+    fn summarize(&self, mut then: impl FnMut(&mut LineHistograms)) -> Snapshot {
+        let mut lines = self
+            .0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter_map(|(&key, histograms)| {
+                let mut histograms = lock(histograms);
+                // Lines which were idle since the last reset are left out, as if never recorded
+                if histograms.events == 0 {
+                    return None;
+                }
+
+                let snapshot = histograms.snapshot(key);
+                then(&mut histograms);
+                Some(snapshot)
+            })
+            .collect::<Vec<_>>();
+        lines.sort_by_key(|line| (line.feed, line.line));
+
+        Snapshot {
+            taken_at: SystemTime::now(),
+            lines,
+        }
+    }
+
+    /// Take a snapshot at every interval on a background thread, and pass it to the exporter.
+    ///
+    /// Each snapshot covers only the events recorded since the previous one.
+    pub fn spawn_exporter<X: Exporter + 'static>(
+        self: &Arc<Self>,
+        interval: Duration,
+        mut exporter: X,
+    ) -> ExporterHandle {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stats = Arc::clone(self);
+        let thread_stop = Arc::clone(&stop);
+
+        let thread = thread::spawn(move || {
+            while !thread_stop.load(Ordering::Acquire) {
+                thread::park_timeout(interval);
+                if thread_stop.load(Ordering::Acquire) {
+                    break;
+                }
+                exporter.export(&stats.take_snapshot());
+            }
+
+            // Flush whatever was recorded since the last interval
+            exporter.export(&stats.take_snapshot());
+        });
+
+        ExporterHandle {
+            stop,
+            thread: Some(thread),
+        }
+    }
+}
+
+/// A destination for periodic latency snapshots
+pub trait Exporter: Send {
+    /// Export a snapshot.
+    fn export(&mut self, snapshot: &Snapshot);
+}
+
+impl<F: FnMut(&Snapshot) + Send> Exporter for F {
+    fn export(&mut self, snapshot: &Snapshot) {
+        self(snapshot)
+    }
+}
+
+/// A running exporter thread, which exports a final snapshot and stops when dropped
+#[derive(Debug)]
+pub struct ExporterHandle {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ExporterHandle {
+    /// Stop the exporter, and wait for it to export its final snapshot.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::Release);
+
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            if thread.join().is_err() {
+                tracing::error!("Latency exporter thread panicked");
+            }
+        }
+    }
+}
+
+impl Drop for ExporterHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::HiTimeDelta;
+
+    const FEED: [u8; 2] = *b"XQ";
+
+    /// A breakdown with every stage set to the given number of nanoseconds.
+    fn breakdown(nanos: i64) -> LatencyBreakdown {
+        let delta = Some(HiTimeDelta::from(nanos));
+        LatencyBreakdown {
+            exchange_to_receive: delta,
+            receive_to_transmit: delta,
+            transmit_to_xcapi_receive: delta,
+            xcapi_receive_to_callback: delta,
+            exchange_to_callback: delta,
+        }
+    }
+
+    fn line_count(stats: &LatencyStats) -> usize {
+        stats.0.read().unwrap().len()
+    }
+
+    #[test]
+    fn negative_samples_are_counted_separately() {
+        let stats = LatencyStats::new();
+        let feed = FeedId::from(FEED);
+        stats.record_breakdown(feed, 1, &breakdown(1_000));
+        stats.record_breakdown(feed, 1, &breakdown(-1_000));
+        stats.record_breakdown(
+            feed,
+            1,
+            &LatencyBreakdown {
+                exchange_to_receive: Some(HiTimeDelta::from(-5)),
+                ..breakdown(2_000)
+            },
+        );
+
+        let snapshot = stats.snapshot();
+        let line = &snapshot.lines[0];
+        let exchange = line.hop(Hop::ExchangeToReceive);
+        assert_eq!((exchange.count, exchange.negative), (1, 2));
+        assert_eq!(exchange.max, Duration::from_nanos(1_000));
+
+        let callback = line.hop(Hop::XcapiReceiveToCallback);
+        assert_eq!((callback.count, callback.negative), (2, 1));
+        assert!(callback.max >= Duration::from_nanos(2_000));
+    }
+
+    #[test]
+    fn missing_stages_are_not_recorded() {
+        let stats = LatencyStats::new();
+        stats.record_breakdown(FeedId::from(FEED), 1, &LatencyBreakdown::default());
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.lines.len(), 1);
+        for hop in Hop::ALL {
+            assert_eq!(*snapshot.lines[0].hop(hop), HopSnapshot::default());
+        }
+    }
+
+    #[test]
+    fn snapshot_leaves_histograms() {
+        let stats = LatencyStats::new();
+        stats.record_breakdown(FeedId::from(FEED), 1, &breakdown(1_000));
+
+        let first = stats.snapshot();
+        let second = stats.snapshot();
+        assert_eq!(first.lines, second.lines);
+        assert_eq!(first.lines[0].hop(Hop::ExchangeToCallback).count, 1);
+    }
+
+    #[test]
+    fn take_snapshot_resets_in_place() {
+        let stats = LatencyStats::new();
+        let feed = FeedId::from(FEED);
+        stats.record_breakdown(feed, 1, &breakdown(1_000));
+        stats.record_breakdown(feed, 1, &breakdown(-1_000));
+
+        let taken = stats.take_snapshot();
+        let hop = taken.lines[0].hop(Hop::ExchangeToReceive);
+        assert_eq!((hop.count, hop.negative), (1, 1));
+
+        // The line's histograms are kept, but emptied
+        assert_eq!(line_count(&stats), 1);
+        {
+            let lines = stats.0.read().unwrap();
+            let histograms = lock(&lines[&(feed, 1)]);
+            assert_eq!(histograms.events, 0);
+            for hop in &histograms.hops {
+                assert_eq!((hop.histogram.len(), hop.negative), (0, 0));
+            }
+        }
+
+        stats.record_breakdown(feed, 1, &breakdown(3_000));
+        let taken = stats.take_snapshot();
+        let hop = taken.lines[0].hop(Hop::ExchangeToReceive);
+        assert_eq!((hop.count, hop.negative), (1, 0));
+        assert!(hop.min >= Duration::from_nanos(3_000));
+    }
+
+    #[test]
+    fn take_snapshot_drops_idle_lines() {
+        let stats = LatencyStats::new();
+        let feed = FeedId::from(FEED);
+        stats.record_breakdown(feed, 1, &breakdown(1_000));
+        stats.record_breakdown(feed, 2, &breakdown(1_000));
+        assert_eq!(stats.take_snapshot().lines.len(), 2);
+
+        assert!(stats.take_snapshot().lines.is_empty());
+        assert!(stats.snapshot().lines.is_empty());
+
+        stats.record_breakdown(feed, 2, &breakdown(1_000));
+        let lines = stats.take_snapshot().lines;
+        assert_eq!(lines.len(), 1);
+        assert_eq!((lines[0].feed, lines[0].line), (feed, 2));
+        assert_eq!(line_count(&stats), 2);
+    }
+
+    #[test]
+    fn lines_are_sorted() {
+        let stats = LatencyStats::new();
+        let keys = [(*b"ZZ", 1), (*b"AA", 2), (*b"AA", 1), (*b"MM", 7)];
+        for (feed, line) in keys {
+            stats.record_breakdown(FeedId::from(feed), line, &breakdown(1_000));
+        }
+
+        let lines = stats
+            .snapshot()
+            .lines
+            .into_iter()
+            .map(|line| (line.feed, line.line))
+            .collect::<Vec<_>>();
+        let mut sorted = keys.map(|(feed, line)| (FeedId::from(feed), line)).to_vec();
+        sorted.sort();
+        assert_eq!(lines, sorted);
+    }
+}
@@ -1,5 +1,6 @@
 //! Timing Groups
 
+use crate::{HiTime, HiTimeDelta};
 use ref_cast::RefCast;
 use rxegy_sys::XC_GROUP_EVENT_TIMING;
 
@@ -43,12 +44,67 @@ impl EventTiming {
         wrap_in_option(self.0.xtev_xcapi_callback_hitime)
     }
 
-    /// Record the spans indicated by this timing group.
+    /// Compute the time spent between each stage of the event's delivery.
+    pub fn latency_breakdown(&self) -> LatencyBreakdown {
+        let exchange = self.exchange_timestamp();
+        let receive = self.appliance_receive_timestamp();
+        let transmit = self.appliance_transmit_timestamp();
+        let xcapi_receive = self.xcapi_receive_timestamp();
+        let callback = self.xcapi_callback_timestamp();
+
+        LatencyBreakdown {
+            exchange_to_receive: hop(exchange, receive),
+            receive_to_transmit: hop(receive, transmit),
+            transmit_to_xcapi_receive: hop(transmit, xcapi_receive),
+            xcapi_receive_to_callback: hop(xcapi_receive, callback),
+            exchange_to_callback: hop(exchange, callback),
+        }
+    }
+
+    /// Record the latency of each stage indicated by this timing group.
+    ///
+    /// A span cannot be opened at a time in the past, so the breakdown is recorded as a single
+    /// `rxegy::latency` trace event, with one field per stage in nanoseconds. Stages with a missing
+    /// timestamp are left empty.
     pub fn record_spans(&self) {
-        // TODO: hook into tracing
+        let breakdown = self.latency_breakdown();
+
+        tracing::trace!(
+            target: "rxegy::latency",
+            exchange_sequence = self.exchange_sequence(),
+            exchange_to_receive_ns = breakdown.exchange_to_receive.map(i64::from),
+            receive_to_transmit_ns = breakdown.receive_to_transmit.map(i64::from),
+            transmit_to_xcapi_receive_ns = breakdown.transmit_to_xcapi_receive.map(i64::from),
+            xcapi_receive_to_callback_ns = breakdown.xcapi_receive_to_callback.map(i64::from),
+            exchange_to_callback_ns = breakdown.exchange_to_callback.map(i64::from),
+            "event latency"
+        );
     }
 }
 
+/// The time spent between each stage of an event's delivery
+///
+/// Each stage is signed, as the exchange's clock is not synchronized with the appliance's, and is
+/// `None` when either of its timestamps was not provided.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LatencyBreakdown {
+    /// From the exchange timestamp to receipt by the appliance.
+    pub exchange_to_receive: Option<HiTimeDelta>,
+    /// From receipt by the appliance to transmission by the appliance.
+    pub receive_to_transmit: Option<HiTimeDelta>,
+    /// From transmission by the appliance to receipt by the XCAPI library.
+    pub transmit_to_xcapi_receive: Option<HiTimeDelta>,
+    /// From receipt by the XCAPI library to the callback being fired.
+    pub xcapi_receive_to_callback: Option<HiTimeDelta>,
+    /// From the exchange timestamp to the callback being fired.
+    pub exchange_to_callback: Option<HiTimeDelta>,
+}
+
+#[inline(always)]
+fn hop(from: Option<u64>, to: Option<u64>) -> Option<HiTimeDelta> {
+    Some(HiTime::from(to?) - HiTime::from(from?))
+}
+
 #[inline(always)]
 fn wrap_in_option(value: u64) -> Option<u64> {
     if value == 0 { None } else { Some(value) }